@@ -0,0 +1,69 @@
+//! Compares generating many IDs one call at a time against the batch API of
+//! [IdGenerator], run with `cargo bench`.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion, Throughput};
+use randid::{IdGenerator, RngSource};
+
+/// Number of IDs generated per iteration.
+const IDS: usize = 10_000;
+
+/// Length of each generated ID.
+const LENGTH: usize = 16;
+
+#[allow(deprecated)]
+fn single(c: &mut Criterion) {
+    let mut group = c.benchmark_group("single");
+    group.throughput(Throughput::Elements(IDS as u64));
+
+    group.bench_function("randid_str", |b| {
+        b.iter(|| {
+            (0..IDS)
+                .map(|_| randid::randid_str(LENGTH as i32))
+                .collect::<Vec<_>>()
+        })
+    });
+    group.bench_function("randid_string", |b| {
+        b.iter(|| {
+            (0..IDS)
+                .map(|_| randid::randid_string(LENGTH).unwrap())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    group.finish();
+}
+
+fn batch(c: &mut Criterion) {
+    let mut group = c.benchmark_group("batch");
+    group.throughput(Throughput::Elements(IDS as u64));
+
+    for &(name, source) in [("thread", RngSource::Thread), ("os", RngSource::Os)].iter() {
+        let gen = IdGenerator::builder()
+            .length(LENGTH)
+            .rng(source)
+            .build()
+            .unwrap();
+
+        group.bench_with_input(BenchmarkId::new("generate", name), &gen, |b, gen| {
+            b.iter(|| (0..IDS).map(|_| gen.generate()).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("generate_n", name), &gen, |b, gen| {
+            b.iter(|| gen.generate_n(IDS))
+        });
+        group.bench_with_input(BenchmarkId::new("iter", name), &gen, |b, gen| {
+            b.iter(|| gen.iter().take(IDS).collect::<Vec<_>>())
+        });
+        group.bench_with_input(BenchmarkId::new("fill", name), &gen, |b, gen| {
+            b.iter_batched_ref(
+                || gen.generate_n(IDS),
+                |ids| gen.fill(ids),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, single, batch);
+criterion_main!(benches);
@@ -0,0 +1,301 @@
+//! Validated character sets for use in generated IDs, see [Alphabet].
+
+use crate::ulid::CROCKFORD;
+use crate::{BASE62, DIGITS, NANOID_ALPHABET};
+use rand::RngCore;
+use std::collections::HashSet;
+use std::fmt;
+use std::str::FromStr;
+
+/// Reasons an [Alphabet] may be rejected when being created.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlphabetError {
+    /// The alphabet contained no characters.
+    Empty,
+    /// The alphabet contained the given character more than once.
+    Duplicate(char),
+    /// The alphabet was created as ASCII-only but contained the given non-ASCII
+    /// character.
+    NonAscii(char),
+    /// The alphabet contained the given character, which isn't an ASCII digit,
+    /// when used with a scheme which only supports digits.
+    NonDigit(char),
+    /// The alphabet has more symbols than the algorithm using it supports.
+    TooLarge {
+        /// Number of symbols in the alphabet.
+        size: usize,
+        /// Maximum number of symbols supported.
+        max: usize,
+    },
+    /// The alphabet has fewer symbols than the algorithm using it requires.
+    TooSmall {
+        /// Number of symbols in the alphabet.
+        size: usize,
+        /// Minimum number of symbols required.
+        min: usize,
+    },
+    /// The alphabet has an odd number of symbols, which the algorithm using it
+    /// can't support without missing errors.
+    OddSize {
+        /// Number of symbols in the alphabet.
+        size: usize,
+    },
+}
+
+impl fmt::Display for AlphabetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AlphabetError::Empty => write!(f, "alphabet must not be empty"),
+            AlphabetError::Duplicate(c) => write!(f, "alphabet contains {:?} more than once", c),
+            AlphabetError::NonAscii(c) => {
+                write!(f, "alphabet contains non-ascii character {:?}", c)
+            }
+            AlphabetError::NonDigit(c) => write!(f, "alphabet contains non-digit {:?}", c),
+            AlphabetError::TooLarge { size, max } => write!(
+                f,
+                "alphabet has {} symbols but at most {} are supported",
+                size, max
+            ),
+            AlphabetError::TooSmall { size, min } => write!(
+                f,
+                "alphabet has {} symbols but at least {} are required",
+                size, min
+            ),
+            AlphabetError::OddSize { size } => write!(
+                f,
+                "alphabet has {} symbols but an even number is required",
+                size
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AlphabetError {}
+
+/// A validated, non-empty set of unique characters which IDs are drawn from.
+///
+/// Alphabets may contain any Unicode characters when created with [Alphabet::new]
+/// or be restricted to ASCII using [Alphabet::ascii], which is useful for things
+/// like DNS labels or other places where multi-byte characters aren't allowed.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{Alphabet, IdGenerator};
+///
+/// fn main() {
+///     let lowercase = Alphabet::ascii("abcdefghijklmnopqrstuvwxyz").unwrap();
+///
+///     assert_eq!(lowercase.size(), 26);
+///     assert!(Alphabet::new("abca").is_err()); // duplicate `a`
+///
+///     let dns_label = IdGenerator::builder()
+///         .alphabet(lowercase)
+///         .length(12)
+///         .build()
+///         .unwrap();
+///
+///     println!("{}.example.com", dns_label.generate()); // like `qhzmcbtavkfe.example.com`
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Alphabet {
+    symbols: Vec<char>,
+    ascii: bool,
+}
+
+impl Alphabet {
+    /// Creates a new alphabet from the characters of `symbols`, which may be any
+    /// Unicode characters.
+    ///
+    /// The order of `symbols` is kept and is significant, as it decides which
+    /// character each random draw maps to.
+    pub fn new(symbols: &str) -> Result<Self, AlphabetError> {
+        let symbols: Vec<char> = symbols.chars().collect();
+
+        if symbols.is_empty() {
+            return Err(AlphabetError::Empty);
+        }
+
+        let mut seen = HashSet::with_capacity(symbols.len());
+
+        for c in symbols.iter() {
+            if !seen.insert(c) {
+                return Err(AlphabetError::Duplicate(*c));
+            }
+        }
+
+        let ascii = symbols.iter().all(char::is_ascii);
+
+        Ok(Self { symbols, ascii })
+    }
+
+    /// Creates a new alphabet like [Alphabet::new] but also rejects any non-ASCII
+    /// characters.
+    pub fn ascii(symbols: &str) -> Result<Self, AlphabetError> {
+        if let Some(c) = symbols.chars().find(|c| !c.is_ascii()) {
+            return Err(AlphabetError::NonAscii(c));
+        }
+
+        Self::new(symbols)
+    }
+
+    /// The BASE62 alphabet of `0-9`, `A-Z` and `a-z`, used by default.
+    pub fn base62() -> Self {
+        Self::from_trusted(BASE62)
+    }
+
+    /// The url-safe alphabet of Nano ID, made of `A-Za-z0-9_-`, see
+    /// [NANOID_ALPHABET].
+    pub fn nanoid() -> Self {
+        Self::from_trusted(NANOID_ALPHABET)
+    }
+
+    /// The ten decimal digits `0-9`.
+    pub fn digits() -> Self {
+        Self::from_trusted(DIGITS)
+    }
+
+    /// Crockford's base32 alphabet of `0-9` and `A-Z` without `I`, `L`, `O` and
+    /// `U`, for IDs which are read aloud or typed in by hand.
+    ///
+    /// Leaving out the letters easily confused with `0` and `1` (and `U`, to avoid
+    /// accidental obscenities) makes IDs unambiguous over the phone, and
+    /// [normalize_readable](crate::normalize_readable) accepts lowercase and the
+    /// common misreadings when parsing them back. Each symbol gives 5 bits of
+    /// entropy, compared to almost 6 for [Alphabet::base62].
+    pub fn readable() -> Self {
+        Self::from_trusted(CROCKFORD)
+    }
+
+    /// Number of unique symbols in this alphabet.
+    pub fn size(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Bits of entropy each randomly drawn symbol provides, i.e. `log2(size)`.
+    pub fn entropy_per_symbol(&self) -> f64 {
+        (self.size() as f64).log2()
+    }
+
+    /// The symbols of this alphabet, in order.
+    pub fn symbols(&self) -> &[char] {
+        &self.symbols
+    }
+
+    /// Returns `true` if every symbol is a single-byte ASCII character.
+    pub fn is_ascii(&self) -> bool {
+        self.ascii
+    }
+
+    /// Position of `c` in this alphabet, if it is a member.
+    pub fn index_of(&self, c: char) -> Option<usize> {
+        self.symbols.iter().position(|s| *s == c)
+    }
+
+    /// Returns `true` if `c` is a member of this alphabet.
+    pub fn contains(&self, c: char) -> bool {
+        self.index_of(c).is_some()
+    }
+
+    /// Draws a single symbol from this alphabet, with every symbol being equally
+    /// likely regardless of the alphabet's size.
+    ///
+    /// Rejection sampling is used to avoid the modulo bias of picking symbols
+    /// using `rng.gen::<usize>() % size`.
+    pub fn sample<R: RngCore + ?Sized>(&self, rng: &mut R) -> char {
+        self.symbols[uniform_index(rng, self.size())]
+    }
+
+    /// Creates an alphabet from a constant already known to be valid.
+    fn from_trusted(symbols: &str) -> Self {
+        Self {
+            symbols: symbols.chars().collect(),
+            ascii: symbols.is_ascii(),
+        }
+    }
+}
+
+/// Draws a uniformly distributed index in `0..n` using rejection sampling.
+///
+/// A random [u32] is masked down to the smallest power of two covering `n` and
+/// redrawn whenever the result lands outside of `0..n`. Every accepted value is
+/// therefore equally likely, unlike `rng.gen::<usize>() % n` which favours the
+/// lower indices whenever `n` doesn't evenly divide the range of the draw. As the
+/// mask is at most twice `n`, fewer than two draws are needed on average.
+///
+/// # Panics
+///
+/// Panics if `n` is `0` or doesn't fit into a [u32].
+pub(crate) fn uniform_index<R: RngCore + ?Sized>(rng: &mut R, n: usize) -> usize {
+    assert!(
+        n != 0 && n <= u32::MAX as usize,
+        "cannot sample from {} symbols",
+        n
+    );
+
+    let mask = (n as u32)
+        .checked_next_power_of_two()
+        .map_or(u32::MAX, |p| p - 1);
+
+    loop {
+        let candidate = (rng.next_u32() & mask) as usize;
+
+        if candidate < n {
+            return candidate;
+        }
+    }
+}
+
+impl Default for Alphabet {
+    fn default() -> Self {
+        Self::base62()
+    }
+}
+
+impl FromStr for Alphabet {
+    type Err = AlphabetError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+impl fmt::Display for Alphabet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for c in self.symbols.iter() {
+            write!(f, "{}", c)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the validation rules of [Alphabet::new] and [Alphabet::ascii]
+    #[test]
+    fn validation() {
+        assert_eq!(Err(AlphabetError::Empty), Alphabet::new(""));
+        assert_eq!(Err(AlphabetError::Duplicate('b')), Alphabet::new("abcb"));
+        assert_eq!(Err(AlphabetError::NonAscii('é')), Alphabet::ascii("abé"));
+
+        let unicode = Alphabet::new("abé").unwrap();
+
+        assert!(!unicode.is_ascii());
+        assert_eq!(3, unicode.size());
+    }
+
+    /// Checks the size and entropy of the built-in presets
+    #[test]
+    fn presets() {
+        assert_eq!(62, Alphabet::base62().size());
+        assert_eq!(10, Alphabet::digits().size());
+        assert!(Alphabet::base62().is_ascii());
+        assert!((Alphabet::base62().entropy_per_symbol() - 5.954).abs() < 0.001);
+        assert_eq!(1.0, Alphabet::new("01").unwrap().entropy_per_symbol());
+        assert_eq!(BASE62, Alphabet::base62().to_string());
+    }
+}
@@ -0,0 +1,163 @@
+//! Entropy and collision estimates for choosing ID lengths.
+//!
+//! Randomly generated IDs aren't guaranteed to be unique, but the chance of a
+//! collision can be made negligible by choosing a long enough length. The
+//! [birthday problem](https://en.wikipedia.org/wiki/Birthday_problem) gives the
+//! chance of any two IDs colliding after generating `n` of them from `2^bits`
+//! possibilities as roughly `1 - e^(-n^2 / 2^(bits + 1))`, which is what these
+//! functions are built on.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use randid::analysis::{self, Analysis};
+//!
+//! fn main() {
+//!     // 16 BASE62 characters, the default length of an `IdGenerator`
+//!     let default = Analysis::new(62, 16);
+//!
+//!     assert_eq!(default.entropy_bits.round(), 95.0);
+//!     println!("{:.3e} IDs before a 1% chance of a collision", default.ids_for_1_percent);
+//!
+//!     // a billion IDs with a one in a million chance of any collision
+//!     assert_eq!(analysis::min_length(62, 1e9, 1e-6), 14);
+//! }
+//! ```
+
+/// Estimates for IDs of a given amount of entropy, created using
+/// [Analysis::new], [Analysis::from_bits] or
+/// [IdGenerator::analysis](crate::IdGenerator::analysis).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Analysis {
+    /// Bits of entropy in each ID.
+    pub entropy_bits: f64,
+    /// Expected number of IDs which can be generated before the chance of any
+    /// collision reaches 1%.
+    pub ids_for_1_percent: f64,
+    /// Expected number of IDs which can be generated before the chance of any
+    /// collision reaches 50%.
+    pub ids_for_50_percent: f64,
+}
+
+impl Analysis {
+    /// Analyses IDs of `length` symbols drawn uniformly from an alphabet of
+    /// `alphabet_size` symbols.
+    pub fn new(alphabet_size: usize, length: usize) -> Self {
+        Self::from_bits(entropy_bits(alphabet_size, length))
+    }
+
+    /// Analyses IDs with `entropy_bits` bits of entropy, such as the 122 random
+    /// bits of a version 4 [Uuid](crate::Uuid).
+    pub fn from_bits(entropy_bits: f64) -> Self {
+        Self {
+            entropy_bits,
+            ids_for_1_percent: ids_for_probability(entropy_bits, 0.01),
+            ids_for_50_percent: ids_for_probability(entropy_bits, 0.5),
+        }
+    }
+
+    /// Chance of any collision after generating `ids` IDs, see
+    /// [collision_probability].
+    pub fn collision_probability(&self, ids: f64) -> f64 {
+        collision_probability(self.entropy_bits, ids)
+    }
+}
+
+/// Bits of entropy in an ID of `length` symbols drawn uniformly from an alphabet
+/// of `alphabet_size` symbols, which is `length * log2(alphabet_size)`.
+pub fn entropy_bits(alphabet_size: usize, length: usize) -> f64 {
+    length as f64 * (alphabet_size as f64).log2()
+}
+
+/// Chance of any two of `ids` IDs with `entropy_bits` bits of entropy being the
+/// same, between `0` and `1`.
+pub fn collision_probability(entropy_bits: f64, ids: f64) -> f64 {
+    if ids < 2.0 {
+        return 0.0;
+    }
+
+    let pairs = ids * (ids - 1.0) / 2.0;
+
+    -(-pairs * (-entropy_bits).exp2()).exp_m1()
+}
+
+/// Expected number of IDs with `entropy_bits` bits of entropy which can be
+/// generated before the chance of any collision reaches `probability`.
+///
+/// # Panics
+///
+/// Panics if `probability` isn't between `0` and `1`, exclusive.
+pub fn ids_for_probability(entropy_bits: f64, probability: f64) -> f64 {
+    (2.0 * risk_factor(probability)).sqrt() * (entropy_bits / 2.0).exp2()
+}
+
+/// Minimum number of symbols from an alphabet of `alphabet_size` symbols needed
+/// to keep the chance of any collision among `ids` IDs at or below
+/// `probability`.
+///
+/// # Panics
+///
+/// Panics if `alphabet_size` is below `2`, or if `probability` isn't between `0`
+/// and `1`, exclusive.
+pub fn min_length(alphabet_size: usize, ids: f64, probability: f64) -> usize {
+    assert!(
+        alphabet_size >= 2,
+        "cannot avoid collisions with {} symbols",
+        alphabet_size
+    );
+
+    let pairs = (ids * (ids - 1.0) / 2.0).max(1.0);
+    let bits = (pairs / risk_factor(probability)).log2().max(0.0);
+
+    ((bits / (alphabet_size as f64).log2()).ceil() as usize).max(1)
+}
+
+/// Computes `-ln(1 - probability)`, the number of expected colliding pairs which
+/// gives a chance of `probability` of at least one.
+fn risk_factor(probability: f64) -> f64 {
+    assert!(
+        probability > 0.0 && probability < 1.0,
+        "probability of {} isn't between 0 and 1",
+        probability
+    );
+
+    -(-probability).ln_1p()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the well-known figures for version 4 UUIDs
+    #[test]
+    fn uuid_v4() {
+        let uuid = Analysis::from_bits(122.0);
+
+        assert!((uuid.ids_for_50_percent / 2.71e18 - 1.0).abs() < 0.01);
+        assert!((uuid.collision_probability(uuid.ids_for_50_percent) - 0.5).abs() < 1e-9);
+        assert!((uuid.collision_probability(uuid.ids_for_1_percent) - 0.01).abs() < 1e-9);
+        assert_eq!(0.0, uuid.collision_probability(1.0));
+    }
+
+    /// Checks entropy grows with the alphabet size and length
+    #[test]
+    fn entropy() {
+        assert_eq!(128.0, entropy_bits(256, 16));
+        assert_eq!(0.0, entropy_bits(1, 100));
+        assert_eq!(entropy_bits(62, 22), Analysis::new(62, 22).entropy_bits);
+    }
+
+    /// Checks recommended lengths are the shortest which meet the target
+    #[test]
+    fn recommended_length() {
+        for &(size, ids, probability) in [(62, 1e9, 1e-6), (10, 1e4, 0.01), (32, 1e12, 1e-9)].iter()
+        {
+            let length = min_length(size, ids, probability);
+
+            assert!(Analysis::new(size, length).collision_probability(ids) <= probability);
+            assert!(Analysis::new(size, length - 1).collision_probability(ids) > probability);
+        }
+
+        assert_eq!(1, min_length(62, 1.0, 0.5));
+    }
+}
@@ -0,0 +1,250 @@
+//! BASE62 encoding and decoding of integers and byte strings, using the same
+//! `0-9A-Za-z` alphabet as [randid_string](crate::randid_string).
+//!
+//! This is useful for shortening existing keys, such as auto-increment database
+//! IDs, into the same url-safe form as randomly generated IDs. As the alphabet is
+//! in ascending ASCII order, encoded values of the same length sort in the same
+//! order as the values themselves.
+//!
+//! ## Examples
+//!
+//! ```rust
+//! use randid::base62;
+//!
+//! fn main() {
+//!     let short = base62::encode(1_000_000);
+//!
+//!     assert_eq!(short, "4C92");
+//!     assert_eq!(base62::decode(&short).unwrap(), 1_000_000);
+//! }
+//! ```
+
+use crate::{RandidError, BASE62};
+
+/// Encodes `value` as BASE62 without any padding, so `0` encodes to `"0"`.
+pub fn encode(value: u128) -> String {
+    encode_u128_padded(value, 1)
+}
+
+/// Decodes BASE62 `text` back into a [u128].
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] for empty text,
+/// [RandidError::InvalidCharacter] for characters outside of BASE62, or
+/// [RandidError::Overflow] if the value doesn't fit in a [u128].
+pub fn decode(text: &str) -> Result<u128, RandidError> {
+    if text.is_empty() {
+        return Err(RandidError::ZeroLength);
+    }
+
+    let mut value = 0u128;
+
+    for digit in digits(text) {
+        let digit = digit? as u128;
+
+        value = value
+            .checked_mul(62)
+            .and_then(|value| value.checked_add(digit))
+            .ok_or(RandidError::Overflow)?;
+    }
+
+    Ok(value)
+}
+
+/// Encodes `bytes` as a single big-endian number in BASE62.
+///
+/// Each leading zero byte is kept as a leading `0` character, so decoding with
+/// [decode_bytes] always gives back the original bytes, including their length.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::base62;
+///
+/// fn main() {
+///     let encoded = base62::encode_bytes(&[0, 0, 1, 0]);
+///
+///     assert_eq!(encoded, "0048");
+///     assert_eq!(base62::decode_bytes(&encoded).unwrap(), vec![0, 0, 1, 0]);
+/// }
+/// ```
+pub fn encode_bytes(bytes: &[u8]) -> String {
+    let zeros = bytes.iter().take_while(|byte| **byte == 0).count();
+    let mut encoded = "0".repeat(zeros);
+
+    encoded.push_str(&encode_bytes_padded(&bytes[zeros..], 0));
+
+    encoded
+}
+
+/// Decodes BASE62 `text` produced by [encode_bytes] back into bytes, with each
+/// leading `0` character becoming a leading zero byte.
+///
+/// # Errors
+///
+/// Returns [RandidError::InvalidCharacter] for characters outside of BASE62.
+pub fn decode_bytes(text: &str) -> Result<Vec<u8>, RandidError> {
+    let zeros = text.chars().take_while(|c| *c == '0').count();
+    let digits = digits(text).collect::<Result<Vec<u8>, RandidError>>()?;
+    let mut decoded = vec![0; zeros];
+
+    decoded.extend(convert_base(&digits, 62, 256));
+
+    Ok(decoded)
+}
+
+/// Encodes `value` as BASE62, left-padded with `0` to `width` characters.
+pub(crate) fn encode_u128_padded(mut value: u128, width: usize) -> String {
+    let symbols = BASE62.as_bytes();
+    let mut encoded = Vec::with_capacity(width);
+
+    while value > 0 {
+        encoded.push(symbols[(value % 62) as usize]);
+        value /= 62;
+    }
+
+    while encoded.len() < width {
+        encoded.push(b'0');
+    }
+
+    encoded.reverse();
+
+    String::from_utf8(encoded).expect("BASE62 is ascii")
+}
+
+/// Encodes `bytes` as a single big-endian number in BASE62, left-padded with `0`
+/// to `width` characters.
+pub(crate) fn encode_bytes_padded(bytes: &[u8], width: usize) -> String {
+    let symbols = BASE62.as_bytes();
+    let digits = convert_base(bytes, 256, 62);
+    let padding = width.saturating_sub(digits.len());
+
+    std::iter::repeat(b'0')
+        .take(padding)
+        .chain(digits.into_iter().map(|digit| symbols[digit as usize]))
+        .map(char::from)
+        .collect()
+}
+
+/// Decodes BASE62 `text` as a single big-endian number into exactly `len` bytes.
+///
+/// Returns [RandidError::InvalidCharacter] for characters outside of BASE62, or
+/// [RandidError::Overflow] if the number doesn't fit in `len` bytes.
+pub(crate) fn decode_bytes_padded(text: &str, len: usize) -> Result<Vec<u8>, RandidError> {
+    let digits = digits(text).collect::<Result<Vec<u8>, RandidError>>()?;
+    let bytes = convert_base(&digits, 62, 256);
+
+    if bytes.len() > len {
+        return Err(RandidError::Overflow);
+    }
+
+    let mut decoded = vec![0; len - bytes.len()];
+    decoded.extend(bytes);
+
+    Ok(decoded)
+}
+
+/// Maps each character of `text` to its BASE62 digit value.
+fn digits(text: &str) -> impl Iterator<Item = Result<u8, RandidError>> + '_ {
+    text.chars().enumerate().map(|(position, character)| {
+        BASE62
+            .find(character)
+            .map(|digit| digit as u8)
+            .ok_or(RandidError::InvalidCharacter {
+                character,
+                position,
+            })
+    })
+}
+
+/// Converts a big-endian number made of `digits` in base `from` into big-endian
+/// digits in base `to` by repeated long division, without leading zeros.
+fn convert_base(digits: &[u8], from: u32, to: u32) -> Vec<u8> {
+    let mut number: Vec<u32> = digits
+        .iter()
+        .map(|digit| *digit as u32)
+        .skip_while(|digit| *digit == 0)
+        .collect();
+    let mut converted = Vec::new();
+
+    while !number.is_empty() {
+        let mut remainder = 0;
+        let mut quotient = Vec::with_capacity(number.len());
+
+        for digit in number {
+            let accumulated = remainder * from + digit;
+            let next = accumulated / to;
+
+            remainder = accumulated % to;
+
+            if !quotient.is_empty() || next != 0 {
+                quotient.push(next);
+            }
+        }
+
+        converted.push(remainder as u8);
+        number = quotient;
+    }
+
+    converted.reverse();
+    converted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks integers against known encodings and round trips
+    #[test]
+    fn integers() {
+        assert_eq!("0", encode(0));
+        assert_eq!("z", encode(61));
+        assert_eq!("10", encode(62));
+        assert_eq!("7n42DGM5Tflk9n8mt7Fhc7", encode(u128::MAX));
+
+        for value in [0, 1, 61, 62, 3_843, 1_000_000, u64::MAX as u128, u128::MAX] {
+            assert_eq!(value, decode(&encode(value)).unwrap());
+        }
+
+        assert_eq!(5, decode("0005").unwrap());
+    }
+
+    /// Checks malformed or oversized text is rejected
+    #[test]
+    fn integer_errors() {
+        assert_eq!(Err(RandidError::ZeroLength), decode(""));
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '_',
+                position: 2
+            }),
+            decode("ab_c")
+        );
+        assert_eq!(Err(RandidError::Overflow), decode("7n42DGM5Tflk9n8mt7Fhc8"));
+    }
+
+    /// Checks byte strings round trip, including leading zero bytes
+    #[test]
+    fn bytes() {
+        let cases: [&[u8]; 5] = [&[], &[0], &[0, 0, 255], &[1, 3], &[255; 32]];
+
+        for bytes in cases.iter() {
+            assert_eq!(bytes.to_vec(), decode_bytes(&encode_bytes(bytes)).unwrap());
+        }
+
+        assert_eq!("", encode_bytes(&[]));
+        assert_eq!("00", encode_bytes(&[0, 0]));
+        assert_eq!("4B", encode_bytes(&[1, 3]));
+        assert!(decode_bytes("0-").is_err());
+    }
+
+    /// Checks fixed-width encodings are padded and bounded
+    #[test]
+    fn padded() {
+        assert_eq!("00z", encode_u128_padded(61, 3));
+        assert_eq!("000z", encode_bytes_padded(&[61], 4));
+        assert_eq!(vec![0, 0, 61], decode_bytes_padded("z", 3).unwrap());
+        assert_eq!(Err(RandidError::Overflow), decode_bytes_padded("zz", 1));
+    }
+}
@@ -0,0 +1,175 @@
+//! Generating many IDs at once, see [IdGenerator::generate_n], [IdGenerator::fill]
+//! and [IdGenerator::iter].
+
+use crate::{IdGenerator, RngSource};
+use rand::rngs::OsRng;
+use rand::{Error, RngCore};
+use std::fmt;
+
+/// Number of bytes drawn at a time by a [BufferedRng].
+const BLOCK_SIZE: usize = 1024;
+
+/// An infinite iterator of IDs from an [IdGenerator], created using
+/// [IdGenerator::iter].
+///
+/// Randomness is drawn in blocks for the lifetime of the iterator rather than
+/// once per ID, making this the cheapest way to stream IDs one at a time.
+///
+/// # Panics
+///
+/// Iterating panics in the same way as [IdGenerator::generate].
+#[derive(Debug)]
+pub struct Ids<'a> {
+    generator: &'a IdGenerator,
+    rng: Option<BufferedRng>,
+    /// Scratch space for the random symbols of each ID
+    symbols: Vec<char>,
+}
+
+impl<'a> Ids<'a> {
+    pub(crate) fn new(generator: &'a IdGenerator) -> Self {
+        Self {
+            generator,
+            rng: BufferedRng::for_generator(generator),
+            symbols: Vec::new(),
+        }
+    }
+}
+
+impl Iterator for Ids<'_> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        let mut id = String::new();
+        let (generator, symbols) = (self.generator, &mut self.symbols);
+
+        match &mut self.rng {
+            Some(rng) => generator.write_id(rng, symbols, &mut id),
+            None => generator.with_rng(|rng| generator.write_id(rng, symbols, &mut id)),
+        }
+        .expect("blocklist retries were exhausted");
+
+        Some(id)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Source of randomness of a generator which isn't seeded, read [BLOCK_SIZE]
+/// bytes at a time.
+///
+/// This saves looking up [rand::thread_rng] for every ID and, for [OsRng],
+/// making a system call for every symbol. Unused bytes are discarded when it's
+/// dropped.
+pub(crate) struct BufferedRng {
+    inner: Box<dyn RngCore>,
+    block: [u8; BLOCK_SIZE],
+    position: usize,
+}
+
+impl BufferedRng {
+    /// Buffers the configured source of `generator`, or returns [None] if it's
+    /// seeded, as a seeded stream must be consumed exactly as
+    /// [IdGenerator::generate] does to stay reproducible.
+    pub(crate) fn for_generator(generator: &IdGenerator) -> Option<Self> {
+        let inner: Box<dyn RngCore> = match generator.rng() {
+            RngSource::Thread => Box::new(rand::thread_rng()),
+            RngSource::Os => Box::new(OsRng),
+            RngSource::Seeded(_) => return None,
+        };
+
+        Some(Self {
+            inner,
+            block: [0; BLOCK_SIZE],
+            position: BLOCK_SIZE,
+        })
+    }
+}
+
+impl RngCore for BufferedRng {
+    fn next_u32(&mut self) -> u32 {
+        if self.position + 4 > BLOCK_SIZE {
+            self.inner.fill_bytes(&mut self.block);
+            self.position = 0;
+        }
+
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(&self.block[self.position..self.position + 4]);
+        self.position += 4;
+
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let high = self.next_u32() as u64;
+
+        (high << 32) | self.next_u32() as u64
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            chunk.copy_from_slice(&self.next_u32().to_le_bytes()[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.fill_bytes(dest);
+
+        Ok(())
+    }
+}
+
+impl fmt::Debug for BufferedRng {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("BufferedRng").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Blocklist;
+    use std::collections::HashSet;
+
+    /// Checks seeded batches continue the same sequence as single generation
+    #[test]
+    fn seeded_sequence() {
+        let single = IdGenerator::from_seed([0; 32]);
+        let batched = IdGenerator::from_seed([0; 32]);
+
+        let expected: Vec<String> = (0..6).map(|_| single.generate()).collect();
+        let mut filled = vec![String::from("stale"); 2];
+        batched.fill(&mut filled);
+
+        assert_eq!("sW0JzWeBQHtugL3o", filled[0]);
+        assert_eq!(&expected[..2], &filled[..]);
+        assert_eq!(&expected[2..4], &batched.generate_n(2)[..]);
+        assert_eq!(
+            &expected[4..],
+            &batched.iter().take(2).collect::<Vec<_>>()[..]
+        );
+    }
+
+    /// Checks buffered sources produce valid, distinct ids including across
+    /// block boundaries
+    #[test]
+    fn buffered_sources() {
+        for &source in [RngSource::Thread, RngSource::Os].iter() {
+            let gen = IdGenerator::builder()
+                .length(100)
+                .prefix("id_")
+                .rng(source)
+                .blocklist(Blocklist::new(vec!["zz"]))
+                .build()
+                .unwrap();
+
+            let ids = gen.generate_n(50);
+
+            assert!(ids.iter().all(|id| gen.validate(id) && !id.contains("zz")));
+            assert_eq!(50, ids.iter().collect::<HashSet<_>>().len());
+            assert!(gen.iter().take(50).all(|id| gen.validate(&id)));
+        }
+    }
+}
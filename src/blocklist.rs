@@ -0,0 +1,144 @@
+//! Filtering of offensive words out of generated IDs, see [Blocklist].
+
+/// Built-in list of common English profanity and slurs, in lowercase.
+///
+/// Words are kept short where possible so that common variations (plurals,
+/// `-ing` forms and so on) are caught by substring matching too.
+const ENGLISH: &[&str] = &[
+    "anal", "anus", "arse", "ass", "bitch", "bollock", "boob", "butt", "clit", "cock", "coon",
+    "crap", "cum", "cunt", "dick", "dildo", "dyke", "fag", "fuck", "jizz", "kike", "nazi", "nigg",
+    "penis", "piss", "poop", "porn", "pussy", "rape", "retard", "scrot", "sex", "shit", "slut",
+    "spic", "tit", "twat", "vagina", "wank", "whore",
+];
+
+/// A case-insensitive list of words which must never appear in generated IDs.
+///
+/// IDs are checked by substring, so a blocked word anywhere within an ID is
+/// caught regardless of what surrounds it. This is deliberately strict: the
+/// built-in [Blocklist::english] list blocks `ass`, so `class` is also blocked,
+/// which only costs a rare regeneration for random IDs.
+///
+/// Blocklists are opt-in and can be given to
+/// [IdGeneratorBuilder::blocklist](crate::IdGeneratorBuilder::blocklist) and
+/// [ObfuscatorBuilder::blocklist](crate::ObfuscatorBuilder::blocklist).
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::Blocklist;
+///
+/// fn main() {
+///     let blocklist = Blocklist::english().with_words(vec!["acme"]);
+///
+///     assert!(blocklist.is_blocked("x7FUCKq"));
+///     assert!(blocklist.is_blocked("ACMEid"));
+///     assert!(!blocklist.is_blocked("b7Rq2z"));
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Blocklist {
+    words: Vec<String>,
+}
+
+impl Blocklist {
+    /// Creates a blocklist of only the given words, ignoring empty ones.
+    pub fn new<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::default().with_words(words)
+    }
+
+    /// Creates a blocklist of built-in common English profanity and slurs.
+    pub fn english() -> Self {
+        Self::new(ENGLISH.iter().copied())
+    }
+
+    /// Adds the given words to this blocklist, ignoring empty ones and those
+    /// already present.
+    pub fn with_words<I, S>(mut self, words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        for word in words {
+            let word = word.into().to_lowercase();
+
+            if !word.is_empty() && !self.words.contains(&word) {
+                self.words.push(word);
+            }
+        }
+
+        self
+    }
+
+    /// Returns `true` if `text` contains any blocked word, ignoring case.
+    pub fn is_blocked(&self, text: &str) -> bool {
+        if self.words.is_empty() {
+            return false;
+        }
+
+        let text = text.to_lowercase();
+
+        self.words.iter().any(|word| text.contains(word.as_str()))
+    }
+
+    /// Blocked words, in lowercase.
+    pub fn words(&self) -> &[String] {
+        &self.words
+    }
+
+    /// Number of blocked words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Returns `true` if no words are blocked.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Counts of how often the blocklist of an [IdGenerator](crate::IdGenerator) has
+/// triggered, from [IdGenerator::blocklist_metrics](crate::IdGenerator::blocklist_metrics).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BlocklistMetrics {
+    /// Number of generated IDs which contained a blocked word and were
+    /// regenerated.
+    pub rejected: u64,
+    /// Number of times every retry contained a blocked word, so generation
+    /// failed with [RandidError::BlocklistExhausted](crate::RandidError::BlocklistExhausted).
+    pub exhausted: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks matching ignores case and finds words anywhere in the text
+    #[test]
+    fn matching() {
+        let blocklist = Blocklist::new(vec!["Bad", ""]);
+
+        assert_eq!(&["bad".to_string()], blocklist.words());
+        assert!(blocklist.is_blocked("BAD"));
+        assert!(blocklist.is_blocked("xxbAdxx"));
+        assert!(!blocklist.is_blocked("b_a_d"));
+        assert!(!Blocklist::default().is_blocked("bad"));
+    }
+
+    /// Checks the built-in list is lowercase and can be extended without
+    /// duplicates
+    #[test]
+    fn english() {
+        let english = Blocklist::english();
+
+        assert!(english
+            .words()
+            .iter()
+            .all(|word| *word == word.to_lowercase()));
+        assert_eq!(english, english.clone().with_words(vec!["SHIT"]));
+        assert_eq!(english.len() + 1, english.with_words(vec!["acme"]).len());
+    }
+}
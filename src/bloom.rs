@@ -0,0 +1,394 @@
+//! Memory-bounded, probabilistic sets of issued IDs, see [BloomFilter].
+
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+/// Magic bytes at the start of a persisted [BloomFilter].
+const MAGIC: &[u8; 8] = b"RANDIDBF";
+
+/// Version of the persisted format of a [BloomFilter].
+const FORMAT_VERSION: u8 = 1;
+
+/// Most bits set per inserted string, well above the `60` needed for a one in
+/// 10^18 chance of a false positive.
+const MAX_HASHES: u32 = 64;
+
+/// Offset basis of 64-bit FNV-1a.
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// Prime of 64-bit FNV-1a.
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Alternative offset basis giving a second, independent FNV-1a hash.
+const FNV_OFFSET_ALT: u64 = 0x8422_2325_cbf2_9ce4;
+
+/// A bloom filter of strings, which answers whether a string has been inserted
+/// with no false negatives and a small, configurable chance of false positives.
+///
+/// This takes a fixed amount of memory regardless of how many strings are
+/// inserted, making it suitable as a [UniquenessStore](crate::UniquenessStore)
+/// for very high volumes of IDs where keeping every ID in a `HashSet` would be too
+/// large. The trade-off is that a never-issued ID is occasionally reported as a
+/// duplicate, which only costs an extra retry when generating.
+///
+/// Strings are hashed with FNV-1a rather than the randomly keyed hasher of the
+/// standard library, so the same strings always set the same bits across runs,
+/// platforms and versions of randid. This means a filter can be saved with
+/// [BloomFilter::save] at the end of one batch job and loaded with
+/// [BloomFilter::load] at the start of the next, to keep deduplicating across
+/// runs.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::BloomFilter;
+///
+/// fn main() {
+///     // room for a million ids with a one in a million chance of a false positive
+///     let mut filter = BloomFilter::with_false_positive_rate(1_000_000, 1e-6);
+///
+///     assert!(filter.insert("bWk9D"));
+///     assert!(!filter.insert("bWk9D"));
+///     assert!(filter.contains("bWk9D"));
+///     assert_eq!(filter.num_bits() / 8 / 1024, 3510); // about 3.4 MiB
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BloomFilter {
+    bits: Vec<u64>,
+    num_bits: u64,
+    hashes: u32,
+    /// Number of insertions which set at least one new bit
+    inserted: u64,
+}
+
+impl BloomFilter {
+    /// Creates an empty filter of `num_bits` bits which sets `hashes` bits per
+    /// inserted string. Both are raised to at least `1`, and `hashes` is lowered
+    /// to at most `64` and to no more than `num_bits`.
+    pub fn new(num_bits: usize, hashes: u32) -> Self {
+        let num_bits = num_bits.max(1) as u64;
+
+        Self {
+            bits: vec![0; word_count(num_bits) as usize],
+            num_bits,
+            hashes: hashes.min(num_bits.min(MAX_HASHES as u64) as u32).max(1),
+            inserted: 0,
+        }
+    }
+
+    /// Creates an empty filter sized to hold `expected_items` strings while
+    /// keeping the chance of a false positive at or below `rate`.
+    ///
+    /// Uses the optimal `-n ln(p) / ln(2)^2` bits and `ln(2) m / n` hashes. The
+    /// false positive rate rises above `rate` if more than `expected_items`
+    /// strings are inserted, which can be monitored using
+    /// [BloomFilter::false_positive_rate].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rate` isn't between `0` and `1`, exclusive.
+    pub fn with_false_positive_rate(expected_items: u64, rate: f64) -> Self {
+        assert!(
+            rate > 0.0 && rate < 1.0,
+            "false positive rate of {} isn't between 0 and 1",
+            rate
+        );
+
+        let items = expected_items.max(1) as f64;
+        let num_bits = (-items * rate.ln() / std::f64::consts::LN_2.powi(2)).ceil();
+        let hashes = (num_bits / items * std::f64::consts::LN_2).round();
+
+        Self::new(num_bits as usize, hashes as u32)
+    }
+
+    /// Inserts `item`, returning `true` if it definitely wasn't present before or
+    /// `false` if it probably was.
+    pub fn insert(&mut self, item: &str) -> bool {
+        let mut inserted = false;
+
+        for index in self.indexes(item) {
+            let (word, mask) = ((index / 64) as usize, 1 << (index % 64));
+
+            inserted |= self.bits[word] & mask == 0;
+            self.bits[word] |= mask;
+        }
+
+        if inserted {
+            self.inserted += 1;
+        }
+
+        inserted
+    }
+
+    /// Returns `true` if `item` has probably been inserted, or `false` if it
+    /// definitely hasn't.
+    pub fn contains(&self, item: &str) -> bool {
+        self.indexes(item)
+            .all(|index| self.bits[(index / 64) as usize] & 1 << (index % 64) != 0)
+    }
+
+    /// Number of bits in this filter.
+    pub fn num_bits(&self) -> u64 {
+        self.num_bits
+    }
+
+    /// Number of bits set per inserted string.
+    pub fn hashes(&self) -> u32 {
+        self.hashes
+    }
+
+    /// Number of distinct strings inserted, not counting those reported as
+    /// already present.
+    pub fn len(&self) -> u64 {
+        self.inserted
+    }
+
+    /// Returns `true` if nothing has been inserted.
+    pub fn is_empty(&self) -> bool {
+        self.inserted == 0
+    }
+
+    /// Current chance of [BloomFilter::contains] giving a false positive, based
+    /// on the fraction of bits which are set.
+    pub fn false_positive_rate(&self) -> f64 {
+        let set: u64 = self.bits.iter().map(|word| word.count_ones() as u64).sum();
+
+        (set as f64 / self.num_bits as f64).powi(self.hashes as i32)
+    }
+
+    /// Writes this filter to `writer` in a compact, versioned binary format which
+    /// can be read back with [BloomFilter::read_from].
+    pub fn write_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION])?;
+        writer.write_all(&self.hashes.to_le_bytes())?;
+        writer.write_all(&self.num_bits.to_le_bytes())?;
+        writer.write_all(&self.inserted.to_le_bytes())?;
+
+        for word in self.bits.iter() {
+            writer.write_all(&word.to_le_bytes())?;
+        }
+
+        writer.flush()
+    }
+
+    /// Reads a filter written by [BloomFilter::write_to].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error of kind [io::ErrorKind::InvalidData] if the data isn't a
+    /// filter, is of an unsupported version or has more hashes than
+    /// [BloomFilter::new] allows, or any error from `reader`.
+    pub fn read_from<R: Read>(mut reader: R) -> io::Result<Self> {
+        let mut magic = [0; 8];
+        let mut version = [0; 1];
+        reader.read_exact(&mut magic)?;
+        reader.read_exact(&mut version)?;
+
+        if &magic != MAGIC {
+            return Err(invalid_data("not a randid bloom filter"));
+        } else if version[0] != FORMAT_VERSION {
+            return Err(invalid_data("unsupported bloom filter version"));
+        }
+
+        let hashes = u32::from_le_bytes(read_array(&mut reader)?);
+        let num_bits = u64::from_le_bytes(read_array(&mut reader)?);
+        let inserted = u64::from_le_bytes(read_array(&mut reader)?);
+
+        if hashes == 0 || num_bits == 0 {
+            return Err(invalid_data("bloom filter must have bits and hashes"));
+        } else if hashes > MAX_HASHES || hashes as u64 > num_bits {
+            // each hash costs a step on every lookup, so far too many would hang
+            return Err(invalid_data("bloom filter has too many hashes"));
+        }
+
+        // grown as words are read, so a corrupt size can't cause a huge allocation
+        let mut bits = Vec::new();
+
+        for _ in 0..word_count(num_bits) {
+            bits.push(u64::from_le_bytes(read_array(&mut reader)?));
+        }
+
+        Ok(Self {
+            bits,
+            num_bits,
+            hashes,
+            inserted,
+        })
+    }
+
+    /// Saves this filter to the file at `path`, replacing it if it exists.
+    ///
+    /// The filter is written to a temporary file next to `path` which is then
+    /// renamed over it, so a crash part way through leaves any previously saved
+    /// filter intact.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temp_name = path
+            .file_name()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "path has no file name"))?
+            .to_os_string();
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp = path.with_file_name(temp_name);
+
+        let saved = File::create(&temp)
+            .and_then(|file| {
+                let mut writer = BufWriter::new(file);
+                self.write_to(&mut writer)?;
+
+                writer
+                    .into_inner()
+                    .map_err(|err| err.into_error())?
+                    .sync_all()
+            })
+            .and_then(|()| fs::rename(&temp, path));
+
+        if saved.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+
+        saved
+    }
+
+    /// Loads a filter saved with [BloomFilter::save] from the file at `path`.
+    ///
+    /// Errors are the same as [BloomFilter::read_from].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::read_from(BufReader::new(File::open(path)?))
+    }
+
+    /// Bit positions for `item`, derived from two FNV-1a hashes by double hashing.
+    fn indexes(&self, item: &str) -> impl Iterator<Item = u64> {
+        let first = fnv1a(FNV_OFFSET, item.as_bytes());
+        let second = fnv1a(FNV_OFFSET_ALT, item.as_bytes()) | 1;
+        let num_bits = self.num_bits;
+
+        (0..self.hashes as u64).map(move |i| first.wrapping_add(i.wrapping_mul(second)) % num_bits)
+    }
+}
+
+/// Number of 64-bit words needed to hold `num_bits` bits, which must be above `0`.
+fn word_count(num_bits: u64) -> u64 {
+    (num_bits - 1) / 64 + 1
+}
+
+/// Reads exactly `N` bytes from `reader`.
+fn read_array<R: Read, const N: usize>(reader: &mut R) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+
+    Ok(bytes)
+}
+
+/// Creates an [io::Error] for malformed persisted data.
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+/// Hashes `bytes` using 64-bit FNV-1a starting from `offset`.
+fn fnv1a(offset: u64, bytes: &[u8]) -> u64 {
+    bytes.iter().fold(offset, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(FNV_PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the hash against published FNV-1a test vectors, as it must never
+    /// change
+    #[test]
+    fn fnv_vectors() {
+        assert_eq!(0xcbf29ce484222325, fnv1a(FNV_OFFSET, b""));
+        assert_eq!(0xaf63dc4c8601ec8c, fnv1a(FNV_OFFSET, b"a"));
+        assert_eq!(0x85944171f73967e8, fnv1a(FNV_OFFSET, b"foobar"));
+    }
+
+    /// Checks there are no false negatives and few false positives
+    #[test]
+    fn membership() {
+        let mut filter = BloomFilter::new(10_000 * 10, 7);
+
+        let duplicates = (0..10_000)
+            .filter(|i| !filter.insert(&format!("in-{}", i)))
+            .count();
+
+        assert!(duplicates < 200, "{} false duplicates", duplicates);
+        assert!((0..10_000).all(|i| filter.contains(&format!("in-{}", i))));
+
+        let false_positives = (0..10_000)
+            .filter(|i| filter.contains(&format!("out-{}", i)))
+            .count();
+
+        assert!(false_positives < 200, "{} false positives", false_positives);
+    }
+
+    /// Checks filters are sized for their target rate and stay near it when full
+    #[test]
+    fn false_positive_rate() {
+        let mut filter = BloomFilter::with_false_positive_rate(10_000, 0.01);
+
+        assert_eq!(95_851, filter.num_bits());
+        assert_eq!(7, filter.hashes());
+        assert_eq!(64, BloomFilter::new(1_000, 100).hashes());
+        assert_eq!(2, BloomFilter::new(2, 7).hashes());
+
+        for i in 0..10_000 {
+            filter.insert(&format!("in-{}", i));
+        }
+
+        assert!(filter.false_positive_rate() < 0.011);
+        assert!(filter.len() > 9_900);
+    }
+
+    /// Checks filters survive a round trip through a file, and malformed data is
+    /// rejected
+    #[test]
+    fn persistence() {
+        let path = std::env::temp_dir().join(format!("randid-bloom-{}", std::process::id()));
+        let mut filter = BloomFilter::new(1_000, 3);
+        filter.insert("persisted");
+
+        std::fs::write(&path, b"previous run").unwrap();
+        filter.save(&path).unwrap();
+        let loaded = BloomFilter::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let temp = format!("randid-bloom-{0}.{0}.tmp", std::process::id());
+
+        assert!(!path.with_file_name(temp).exists());
+
+        assert_eq!(filter, loaded);
+        assert!(loaded.contains("persisted"));
+
+        let mut bytes = Vec::new();
+        filter.write_to(&mut bytes).unwrap();
+
+        for hashes in [65, u32::MAX] {
+            let mut corrupt = bytes.clone();
+            corrupt[9..13].copy_from_slice(&hashes.to_le_bytes());
+
+            assert_eq!(
+                io::ErrorKind::InvalidData,
+                BloomFilter::read_from(&corrupt[..]).unwrap_err().kind()
+            );
+        }
+
+        assert_eq!(
+            io::ErrorKind::UnexpectedEof,
+            BloomFilter::read_from(&bytes[..bytes.len() - 1])
+                .unwrap_err()
+                .kind()
+        );
+
+        bytes[0] = b'X';
+
+        assert_eq!(
+            io::ErrorKind::InvalidData,
+            BloomFilter::read_from(&bytes[..]).unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Check characters for catching typos in manually entered IDs, see [Checksum].
+
+use crate::{Alphabet, AlphabetError, RandidError};
+
+/// Operation table of the Damm algorithm, a weakly totally anti-symmetric
+/// quasigroup of order 10.
+const DAMM: [[u8; 10]; 10] = [
+    [0, 3, 1, 7, 5, 9, 8, 6, 4, 2],
+    [7, 0, 9, 2, 1, 5, 4, 8, 6, 3],
+    [4, 2, 0, 6, 8, 7, 1, 3, 5, 9],
+    [1, 7, 5, 0, 9, 8, 3, 4, 2, 6],
+    [6, 1, 2, 3, 0, 4, 5, 9, 7, 8],
+    [3, 6, 7, 4, 2, 0, 9, 5, 8, 1],
+    [5, 8, 6, 9, 7, 2, 0, 1, 3, 4],
+    [8, 9, 4, 5, 3, 6, 2, 0, 1, 7],
+    [9, 4, 3, 8, 6, 1, 7, 2, 0, 5],
+    [2, 5, 8, 1, 4, 3, 6, 7, 9, 0],
+];
+
+/// Multiplication table of the dihedral group D5 used by the Verhoeff algorithm.
+const VERHOEFF_D: [[u8; 10]; 10] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 2, 3, 4, 0, 6, 7, 8, 9, 5],
+    [2, 3, 4, 0, 1, 7, 8, 9, 5, 6],
+    [3, 4, 0, 1, 2, 8, 9, 5, 6, 7],
+    [4, 0, 1, 2, 3, 9, 5, 6, 7, 8],
+    [5, 9, 8, 7, 6, 0, 4, 3, 2, 1],
+    [6, 5, 9, 8, 7, 1, 0, 4, 3, 2],
+    [7, 6, 5, 9, 8, 2, 1, 0, 4, 3],
+    [8, 7, 6, 5, 9, 3, 2, 1, 0, 4],
+    [9, 8, 7, 6, 5, 4, 3, 2, 1, 0],
+];
+
+/// Position-dependent permutations of the Verhoeff algorithm.
+const VERHOEFF_P: [[u8; 10]; 8] = [
+    [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+    [1, 5, 7, 6, 2, 8, 3, 0, 9, 4],
+    [5, 8, 0, 3, 7, 9, 6, 1, 4, 2],
+    [8, 9, 1, 6, 0, 4, 3, 5, 2, 7],
+    [9, 4, 5, 3, 1, 2, 7, 6, 8, 0],
+    [4, 2, 8, 6, 5, 7, 3, 9, 0, 1],
+    [2, 7, 9, 3, 8, 0, 6, 4, 1, 5],
+    [7, 0, 4, 6, 9, 1, 3, 2, 5, 8],
+];
+
+/// Inverses within [VERHOEFF_D] of the Verhoeff algorithm.
+const VERHOEFF_INV: [u8; 10] = [0, 4, 3, 2, 1, 5, 6, 7, 8, 9];
+
+/// A scheme for computing a check character over the symbols of an ID, so that
+/// typos can be detected before looking anything up.
+///
+/// | Scheme                  | Symbols      | Single errors | Adjacent transpositions |
+/// |-------------------------|--------------|---------------|-------------------------|
+/// | [Checksum::Luhn]        | `0-9`        | All           | All but `09` ↔ `90`     |
+/// | [Checksum::Damm]        | `0-9`        | All           | All                     |
+/// | [Checksum::Verhoeff]    | `0-9`        | All           | All                     |
+/// | [Checksum::LuhnModN]    | Even-sized   | All           | All but one pair        |
+///
+/// The decimal schemes always produce a check digit from `0-9` and ignore the
+/// alphabet they are given, whereas [Checksum::LuhnModN] draws its check
+/// character from the alphabet, making it suitable for BASE62 or
+/// [readable](Alphabet::readable) IDs. Its alphabet must have an even number of
+/// symbols, as with an odd number the doubling step maps two symbols to the same
+/// value and some single-character errors would go unnoticed.
+///
+/// Check characters can be appended to generated IDs automatically using
+/// [IdGeneratorBuilder::checksum](crate::IdGeneratorBuilder::checksum), and
+/// validated with [IdGenerator::validate](crate::IdGenerator::validate).
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{Alphabet, Checksum};
+///
+/// fn main() {
+///     let digits = Alphabet::digits();
+///
+///     assert_eq!(Checksum::Luhn.check_symbol(&digits, "7992739871").unwrap(), '3');
+///     assert!(Checksum::Luhn.validate(&digits, "79927398713"));
+///     assert!(!Checksum::Luhn.validate(&digits, "79927398731"));
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Checksum {
+    /// The Luhn algorithm used by credit card numbers.
+    Luhn,
+    /// The Damm algorithm, which catches every single-digit error and adjacent
+    /// transposition.
+    Damm,
+    /// The Verhoeff algorithm, which catches every single-digit error and
+    /// adjacent transposition.
+    Verhoeff,
+    /// The Luhn mod N algorithm, generalising [Checksum::Luhn] to the symbols of
+    /// any alphabet with an even number of symbols.
+    LuhnModN,
+}
+
+impl Checksum {
+    /// Computes the check character for `symbols`, which should be appended to
+    /// them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::InvalidCharacter] if `symbols` contains anything
+    /// other than ASCII digits for a decimal scheme, or characters outside of
+    /// `alphabet` for [Checksum::LuhnModN]. Returns [AlphabetError::OddSize] for
+    /// [Checksum::LuhnModN] if `alphabet` has an odd number of symbols.
+    pub fn check_symbol(&self, alphabet: &Alphabet, symbols: &str) -> Result<char, RandidError> {
+        let check = self.check_value(alphabet, &self.values(alphabet, symbols)?);
+
+        Ok(self
+            .symbol(alphabet, check)
+            .expect("check value is within the alphabet"))
+    }
+
+    /// Returns `true` if the last character of `text` is the correct check
+    /// character for the rest, or `false` if it isn't, `text` is malformed or
+    /// `alphabet` isn't supported by this scheme.
+    pub fn validate(&self, alphabet: &Alphabet, text: &str) -> bool {
+        let mut values = match self.values(alphabet, text) {
+            Ok(values) => values,
+            Err(_) => return false,
+        };
+
+        match values.pop() {
+            Some(check) => self.check_value(alphabet, &values) == check,
+            None => false,
+        }
+    }
+
+    /// Returns `true` if this scheme only works on the digits `0-9`.
+    pub fn is_decimal(&self) -> bool {
+        *self != Checksum::LuhnModN
+    }
+
+    /// Numeric values of the characters of `text` under this scheme.
+    fn values(&self, alphabet: &Alphabet, text: &str) -> Result<Vec<usize>, RandidError> {
+        if *self == Checksum::LuhnModN && alphabet.size() % 2 == 1 {
+            return Err(AlphabetError::OddSize {
+                size: alphabet.size(),
+            }
+            .into());
+        }
+
+        text.chars()
+            .enumerate()
+            .map(|(position, character)| {
+                let value = if self.is_decimal() {
+                    character.to_digit(10).map(|digit| digit as usize)
+                } else {
+                    alphabet.index_of(character)
+                };
+
+                value.ok_or(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                })
+            })
+            .collect()
+    }
+
+    /// Character representing the check `value` under this scheme.
+    fn symbol(&self, alphabet: &Alphabet, value: usize) -> Option<char> {
+        if self.is_decimal() {
+            std::char::from_digit(value as u32, 10)
+        } else {
+            alphabet.symbols().get(value).copied()
+        }
+    }
+
+    /// Computes the numeric check value for the numeric `values` of a payload.
+    fn check_value(&self, alphabet: &Alphabet, values: &[usize]) -> usize {
+        match self {
+            Checksum::Luhn => luhn(values, 10),
+            Checksum::Damm => values
+                .iter()
+                .fold(0, |interim, value| DAMM[interim][*value] as usize),
+            Checksum::Verhoeff => {
+                let c = values.iter().rev().enumerate().fold(0, |c, (i, value)| {
+                    VERHOEFF_D[c][VERHOEFF_P[(i + 1) % 8][*value] as usize] as usize
+                });
+
+                VERHOEFF_INV[c] as usize
+            }
+            Checksum::LuhnModN => luhn(values, alphabet.size()),
+        }
+    }
+}
+
+/// Computes the Luhn mod `n` check value of `values`, doubling every other value
+/// starting from the rightmost.
+fn luhn(values: &[usize], n: usize) -> usize {
+    let sum: usize = values
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(i, value)| {
+            let addend = if i % 2 == 0 { value * 2 } else { *value };
+
+            addend / n + addend % n
+        })
+        .sum();
+
+    (n - sum % n) % n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks each scheme against well-known published examples
+    #[test]
+    fn reference_vectors() {
+        let digits = Alphabet::digits();
+
+        assert_eq!(
+            '3',
+            Checksum::Luhn.check_symbol(&digits, "7992739871").unwrap()
+        );
+        assert_eq!('4', Checksum::Damm.check_symbol(&digits, "572").unwrap());
+        assert_eq!(
+            '3',
+            Checksum::Verhoeff.check_symbol(&digits, "236").unwrap()
+        );
+        assert_eq!(
+            '3',
+            Checksum::LuhnModN
+                .check_symbol(&digits, "7992739871")
+                .unwrap()
+        );
+    }
+
+    /// Checks every single-character error and adjacent transposition is caught,
+    /// apart from those documented for each scheme
+    #[test]
+    fn detects_errors() {
+        let base62 = Alphabet::base62();
+        let digits = Alphabet::digits();
+        let six = Alphabet::new("abcdef").unwrap();
+        let cases = [
+            (Checksum::Luhn, &digits, "4815162342"),
+            (Checksum::Damm, &digits, "4815162342"),
+            (Checksum::Verhoeff, &digits, "4815162342"),
+            (Checksum::LuhnModN, &base62, "aZ09kQx7Pm"),
+            (Checksum::LuhnModN, &six, "cadebf"),
+        ];
+
+        for (checksum, alphabet, payload) in cases.iter() {
+            let mut id: Vec<char> = payload.chars().collect();
+            id.push(checksum.check_symbol(alphabet, payload).unwrap());
+
+            assert!(checksum.validate(alphabet, &id.iter().collect::<String>()));
+
+            let symbols = if checksum.is_decimal() {
+                digits.symbols()
+            } else {
+                alphabet.symbols()
+            };
+
+            for position in 0..id.len() {
+                for symbol in symbols.iter().filter(|s| **s != id[position]) {
+                    let mut typo = id.clone();
+                    typo[position] = *symbol;
+
+                    assert!(!checksum.validate(alphabet, &typo.iter().collect::<String>()));
+                }
+
+                if position + 1 < id.len() && id[position] != id[position + 1] {
+                    let mut swapped = id.clone();
+                    swapped.swap(position, position + 1);
+
+                    assert!(!checksum.validate(alphabet, &swapped.iter().collect::<String>()));
+                }
+            }
+        }
+
+        // an odd alphabet would let `b` and `d` double to the same value
+        let odd = Alphabet::new("abcde").unwrap();
+
+        assert_eq!(
+            Err(RandidError::InvalidAlphabet(AlphabetError::OddSize {
+                size: 5
+            })),
+            Checksum::LuhnModN.check_symbol(&odd, "b")
+        );
+        assert!(!Checksum::LuhnModN.validate(&odd, "dd"));
+        assert!(!Checksum::LuhnModN.validate(&odd, "bd"));
+    }
+
+    /// Checks malformed input is rejected rather than validated
+    #[test]
+    fn malformed() {
+        let digits = Alphabet::digits();
+
+        assert!(!Checksum::Damm.validate(&digits, ""));
+        assert!(!Checksum::Luhn.validate(&digits, "12a4"));
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '-',
+                position: 2
+            }),
+            Checksum::Verhoeff.check_symbol(&digits, "12-4")
+        );
+    }
+}
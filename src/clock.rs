@@ -0,0 +1,11 @@
+//! Wall-clock helpers shared by the time-ordered ID formats.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Milliseconds since the Unix epoch, or `0` if the system clock is set before
+/// it.
+pub(crate) fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
@@ -0,0 +1,147 @@
+//! The crate-wide [RandidError] type.
+
+use crate::AlphabetError;
+use std::fmt;
+
+/// Errors which may occur when configuring or generating IDs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RandidError {
+    /// A length of `0` was requested, which would only ever produce empty IDs.
+    ZeroLength,
+    /// The requested length was above the maximum allowed.
+    LengthExceeded {
+        /// Length which was requested.
+        length: usize,
+        /// Maximum length allowed.
+        max: usize,
+    },
+    /// Symbols were to be split into groups of `0`, which can't hold anything.
+    ZeroGroupSize,
+    /// The alphabet given couldn't be used, see [AlphabetError].
+    InvalidAlphabet(AlphabetError),
+    /// Text being parsed as an ID wasn't of an allowed length.
+    InvalidLength {
+        /// Length the text should have been.
+        expected: usize,
+        /// Length the text actually was.
+        found: usize,
+    },
+    /// Text being parsed as an ID didn't start with the expected prefix.
+    InvalidPrefix {
+        /// Prefix the text should have started with.
+        expected: String,
+    },
+    /// Text being parsed as an ID contained a character which isn't allowed.
+    InvalidCharacter {
+        /// The offending character.
+        character: char,
+        /// Character position of `character` within the text.
+        position: usize,
+    },
+    /// A value didn't fit in the bits available to it, such as the random part
+    /// of a monotonic [Ulid](crate::Ulid) running out within a single
+    /// millisecond or parsed text decoding to a number which is too large.
+    Overflow,
+    /// A configured value was above the maximum allowed, such as a snowflake
+    /// worker ID which doesn't fit in its bits.
+    ValueTooLarge {
+        /// Value which was configured.
+        value: u64,
+        /// Maximum value allowed.
+        max: u64,
+    },
+    /// The clock went backwards since the last time-ordered ID was generated,
+    /// so generating another could produce a duplicate.
+    ClockMovedBackwards {
+        /// Milliseconds since the Unix epoch of the last generated ID.
+        last_ms: u64,
+        /// Milliseconds since the Unix epoch the clock reported now.
+        now_ms: u64,
+    },
+    /// Every sequence number for a millisecond has already been used, so another
+    /// ID can't be generated until the next millisecond.
+    SequenceExhausted {
+        /// Milliseconds since the Unix epoch which was exhausted.
+        timestamp_ms: u64,
+    },
+    /// Every attempt at producing an ID contained a blocked word.
+    BlocklistExhausted {
+        /// Number of attempts made before giving up.
+        attempts: usize,
+    },
+    /// Every attempt at producing an ID gave one which had already been issued.
+    UniquenessExhausted {
+        /// Number of attempts made before giving up.
+        attempts: usize,
+    },
+    /// Text being parsed as an ID was well-formed but isn't the form which would
+    /// have been generated for its value, so it was rejected to keep each value
+    /// to exactly one ID.
+    NonCanonical,
+}
+
+impl fmt::Display for RandidError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RandidError::ZeroLength => write!(f, "length must be above zero"),
+            RandidError::LengthExceeded { length, max } => {
+                write!(f, "length of {} exceeds the maximum of {}", length, max)
+            }
+            RandidError::ZeroGroupSize => write!(f, "group size must be above zero"),
+            RandidError::InvalidAlphabet(err) => write!(f, "invalid alphabet, {}", err),
+            RandidError::InvalidLength { expected, found } => {
+                write!(f, "expected a length of {} but found {}", expected, found)
+            }
+            RandidError::InvalidPrefix { expected } => {
+                write!(f, "expected a prefix of {:?}", expected)
+            }
+            RandidError::InvalidCharacter {
+                character,
+                position,
+            } => write!(
+                f,
+                "invalid character {:?} at position {}",
+                character, position
+            ),
+            RandidError::Overflow => write!(f, "value does not fit in the bits available"),
+            RandidError::ValueTooLarge { value, max } => {
+                write!(f, "value of {} exceeds the maximum of {}", value, max)
+            }
+            RandidError::ClockMovedBackwards { last_ms, now_ms } => write!(
+                f,
+                "clock moved backwards from {}ms to {}ms",
+                last_ms, now_ms
+            ),
+            RandidError::SequenceExhausted { timestamp_ms } => {
+                write!(f, "sequence exhausted for {}ms", timestamp_ms)
+            }
+            RandidError::BlocklistExhausted { attempts } => write!(
+                f,
+                "every id contained a blocked word after {} attempts",
+                attempts
+            ),
+            RandidError::UniquenessExhausted { attempts } => write!(
+                f,
+                "every id had already been issued after {} attempts",
+                attempts
+            ),
+            RandidError::NonCanonical => write!(f, "id is not in its canonical form"),
+        }
+    }
+}
+
+impl std::error::Error for RandidError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RandidError::InvalidAlphabet(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<AlphabetError> for RandidError {
+    fn from(err: AlphabetError) -> Self {
+        RandidError::InvalidAlphabet(err)
+    }
+}
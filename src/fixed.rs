@@ -0,0 +1,206 @@
+//! Fixed-length IDs which live on the stack, see [RandId].
+
+use crate::alphabet::uniform_index;
+use crate::{RandidError, BASE62};
+use rand::RngCore;
+use std::borrow::Borrow;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// A random BASE62 ID of exactly `N` characters, stored inline as `[u8; N]`.
+///
+/// This is the allocation-free counterpart of [randid_string](crate::randid_string)
+/// for hot paths: generating one never touches the heap, it's [Copy], and it
+/// derefs to a [str] so it can be used anywhere a `&str` is expected. When an
+/// owned [String] is needed, convert with [String::from] or
+/// [ToString::to_string].
+///
+/// Symbols are drawn in the same way as [Alphabet::sample](crate::Alphabet::sample)
+/// over the BASE62 alphabet, so a `RandId<N>` generated from a seeded generator
+/// matches an [IdGenerator](crate::IdGenerator) of length `N` with the same seed.
+/// `N` should be above `0`, as a `RandId<0>` is always empty.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::RandId;
+///
+/// fn main() {
+///     let id: RandId<12> = RandId::new();
+///     let copy = id;
+///
+///     assert_eq!(id.len(), 12);
+///     assert!(copy.starts_with(&id[..4]));
+///     assert_eq!(id, id.as_str().parse().unwrap());
+///
+///     let owned: String = id.into();
+///     println!("https://example.com/safeid/{}", owned);
+/// }
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RandId<const N: usize>([u8; N]);
+
+impl<const N: usize> RandId<N> {
+    /// Generates a new ID using [rand::thread_rng].
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a new ID using the provided random number generator.
+    pub fn with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let symbols = BASE62.as_bytes();
+        let mut bytes = [0; N];
+
+        for byte in bytes.iter_mut() {
+            *byte = symbols[uniform_index(rng, symbols.len())];
+        }
+
+        Self(bytes)
+    }
+
+    /// The ID as a string slice.
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).expect("base62 is ascii")
+    }
+
+    /// The ID as its raw ASCII bytes.
+    pub fn as_bytes(&self) -> &[u8; N] {
+        &self.0
+    }
+}
+
+impl<const N: usize> Default for RandId<N> {
+    /// Generates a new ID, like [RandId::new].
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Deref for RandId<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for RandId<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Borrow<str> for RandId<N> {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> Hash for RandId<N> {
+    /// Hashes the same as the equivalent [str], so IDs in a `HashMap` can be
+    /// looked up by `&str` through [Borrow].
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+impl<const N: usize> fmt::Debug for RandId<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("RandId").field(&self.as_str()).finish()
+    }
+}
+
+impl<const N: usize> fmt::Display for RandId<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> FromStr for RandId<N> {
+    type Err = RandidError;
+
+    /// Parses an ID of exactly `N` BASE62 characters.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found = s.chars().count();
+
+        if found != N {
+            return Err(RandidError::InvalidLength { expected: N, found });
+        }
+
+        let mut bytes = [0; N];
+
+        for (position, (byte, character)) in bytes.iter_mut().zip(s.chars()).enumerate() {
+            if !BASE62.contains(character) {
+                return Err(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                });
+            }
+
+            *byte = character as u8;
+        }
+
+        Ok(Self(bytes))
+    }
+}
+
+impl<const N: usize> From<RandId<N>> for String {
+    fn from(id: RandId<N>) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::IdGenerator;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha20Rng;
+    use std::collections::HashSet;
+
+    /// Checks seeded ids match the golden values of a seeded [IdGenerator]
+    #[test]
+    fn seeded() {
+        let mut rng = ChaCha20Rng::from_seed([0; 32]);
+        let gen = IdGenerator::from_seed([0; 32]);
+
+        assert_eq!(gen.generate(), RandId::<16>::with_rng(&mut rng).as_str());
+        assert_eq!(
+            "VLOpB8IofSLqnHiB",
+            RandId::<16>::with_rng(&mut rng).to_string()
+        );
+    }
+
+    /// Checks ids can be looked up by `&str` and converted into strings
+    #[test]
+    fn conversions() {
+        let id: RandId<8> = RandId::new();
+        let mut set = HashSet::new();
+        set.insert(id);
+
+        assert!(set.contains(id.as_str()));
+        assert_eq!(id.as_str(), String::from(id));
+        assert_eq!(8, id.chars().filter(|c| c.is_ascii_alphanumeric()).count());
+    }
+
+    /// Checks malformed text is rejected
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(RandidError::InvalidLength {
+                expected: 4,
+                found: 3
+            }),
+            "abc".parse::<RandId<4>>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: 'é',
+                position: 2
+            }),
+            "abéd".parse::<RandId<4>>()
+        );
+    }
+}
@@ -0,0 +1,959 @@
+//! Configurable ID generation through [IdGenerator] and its [IdGeneratorBuilder].
+//!
+//! The free functions such as [randid_str](crate::randid_str) are thin wrappers
+//! over a generator, so if you find yourself repeating the same alphabet, length
+//! or prefix across a codebase it's best to define one [IdGenerator] per kind of
+//! ID and reuse it everywhere.
+
+use crate::analysis::Analysis;
+use crate::batch::{BufferedRng, Ids};
+use crate::{Alphabet, AlphabetError, Blocklist, BlocklistMetrics, Checksum, RandidError};
+use rand::rngs::OsRng;
+use rand::{self, RngCore, SeedableRng};
+use rand_chacha::ChaCha20Rng;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+/// Default maximum number of random symbols an [IdGenerator] may be configured
+/// to produce, see [IdGeneratorBuilder::max_length].
+pub const DEFAULT_MAX_LENGTH: usize = 1024;
+
+/// Default number of times an ID is regenerated before giving up, see
+/// [IdGeneratorBuilder::max_retries] and
+/// [UniqueGenerator::max_retries](crate::UniqueGenerator::max_retries).
+pub const DEFAULT_MAX_RETRIES: usize = 16;
+
+/// Source of randomness used by an [IdGenerator] when calling
+/// [IdGenerator::generate].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RngSource {
+    /// Uses [rand::thread_rng], a fast, lazily-seeded generator local to the
+    /// current thread.
+    #[default]
+    Thread,
+    /// Uses [OsRng], reading directly from the operating system's
+    /// cryptographically secure random number generator.
+    ///
+    /// This is slower than [RngSource::Thread] but should be used whenever the
+    /// generated IDs are secrets, see the [crate-level security
+    /// notes](crate#security).
+    Os,
+    /// Uses a ChaCha20 stream seeded with the given 32 bytes, making the sequence
+    /// of generated IDs fully reproducible.
+    ///
+    /// This is intended for tests, fixtures and golden files and must never be used
+    /// for IDs which act as secrets. See [IdGenerator::from_seed] for the stability
+    /// guarantees of the sequence.
+    Seeded([u8; 32]),
+}
+
+/// A reusable, configured ID generator.
+///
+/// Generators are created using [IdGenerator::builder] and are cheap to use once
+/// built, so a common pattern is to store one per ID kind (e.g. user IDs, order
+/// IDs) and call [IdGenerator::generate] wherever an ID is needed.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::IdGenerator;
+///
+/// fn main() {
+///     let user_ids = IdGenerator::builder()
+///         .length(12)
+///         .prefix("user_")
+///         .build()
+///         .unwrap();
+///
+///     let id = user_ids.generate();
+///
+///     assert!(id.starts_with("user_"));
+///     assert_eq!(id.len(), 17);
+/// }
+/// ```
+#[derive(Debug)]
+pub struct IdGenerator {
+    alphabet: Alphabet,
+    length: usize,
+    prefix: String,
+    suffix: String,
+    group: Option<(usize, char)>,
+    checksum: Option<Checksum>,
+    rng: RngSource,
+    /// Current state of the stream when using [RngSource::Seeded]
+    seeded: Option<Mutex<ChaCha20Rng>>,
+    blocklist: Option<Blocklist>,
+    max_retries: usize,
+    /// Number of IDs rejected by the blocklist, see [BlocklistMetrics::rejected]
+    rejected: AtomicU64,
+    /// Number of failed generations, see [BlocklistMetrics::exhausted]
+    exhausted: AtomicU64,
+}
+
+impl IdGenerator {
+    /// Creates a new [IdGeneratorBuilder] with the default BASE62 alphabet and
+    /// a length of `16`.
+    pub fn builder() -> IdGeneratorBuilder {
+        IdGeneratorBuilder::default()
+    }
+
+    /// Creates a BASE62 generator with a length of `16` which draws from a
+    /// ChaCha20 stream seeded by `seed`, producing the same sequence of IDs every
+    /// time it's created.
+    ///
+    /// Other alphabets and lengths can be seeded by passing [RngSource::Seeded]
+    /// to [IdGeneratorBuilder::rng].
+    ///
+    /// The sequence produced for a given seed and configuration is part of
+    /// randid's public API and won't change between versions of the crate, so it
+    /// can safely be used for snapshot tests and golden files. It is made up of
+    /// the ChaCha20 stream of `rand_chacha` (itself value-stable) and the
+    /// rejection sampling of [Alphabet::sample]. Each symbol takes the next [u32]
+    /// of the stream, masks it down to the bits below the smallest power of two
+    /// that is at least the alphabet's size, and uses the result as an index if
+    /// it's below that size. Otherwise it's discarded and another [u32] is
+    /// taken, so a single symbol can consume several values of the stream. Cloning
+    /// a seeded generator clones its current position in the stream.
+    ///
+    /// Only the prefix, suffix and groups leave the random symbols untouched. A
+    /// [Blocklist] regenerates rejected IDs, consuming more of the stream, and a
+    /// [Checksum] appends a check character, so adding or changing either gives
+    /// different IDs for the same seed.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use randid::IdGenerator;
+    ///
+    /// fn main() {
+    ///     let first = IdGenerator::from_seed([7; 32]);
+    ///     let second = IdGenerator::from_seed([7; 32]);
+    ///
+    ///     assert_eq!(first.generate(), second.generate());
+    /// }
+    /// ```
+    pub fn from_seed(seed: [u8; 32]) -> Self {
+        Self::builder()
+            .rng(RngSource::Seeded(seed))
+            .build()
+            .expect("default configuration is valid")
+    }
+
+    /// Generates a new ID using the configured [RngSource].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a blocklist is configured and every retry contained a blocked
+    /// word, use [IdGenerator::try_generate] to handle this instead. With the
+    /// default retries and a reasonable length this is vanishingly unlikely.
+    pub fn generate(&self) -> String {
+        self.with_rng(|rng| self.generate_with_rng(rng))
+    }
+
+    /// Generates a new ID using the configured [RngSource], returning an error
+    /// instead of panicking if the blocklist can't be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::BlocklistExhausted] if a blocklist is configured and
+    /// the initial ID and every retry contained a blocked word.
+    pub fn try_generate(&self) -> Result<String, RandidError> {
+        self.with_rng(|rng| self.try_generate_with_rng(rng))
+    }
+
+    /// Generates a new ID using the provided random number generator instead of
+    /// the configured [RngSource].
+    ///
+    /// Panics in the same way as [IdGenerator::generate].
+    pub fn generate_with_rng<R: RngCore + ?Sized>(&self, rng: &mut R) -> String {
+        self.try_generate_with_rng(rng)
+            .expect("blocklist retries were exhausted")
+    }
+
+    /// Generates a new ID using the provided random number generator instead of
+    /// the configured [RngSource].
+    ///
+    /// Errors are the same as [IdGenerator::try_generate].
+    pub fn try_generate_with_rng<R: RngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+    ) -> Result<String, RandidError> {
+        let mut generated = String::new();
+        self.write_id(rng, &mut Vec::new(), &mut generated)?;
+
+        Ok(generated)
+    }
+
+    /// Generates `n` IDs at once using the configured [RngSource].
+    ///
+    /// This is faster than calling [IdGenerator::generate] `n` times, as
+    /// randomness is drawn in large blocks and the source is only looked up once,
+    /// which matters most for [RngSource::Os] as it avoids a system call per
+    /// symbol.
+    /// Seeded generators give exactly the IDs which `n` calls to
+    /// [IdGenerator::generate] would have.
+    ///
+    /// Panics in the same way as [IdGenerator::generate].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use randid::IdGenerator;
+    ///
+    /// fn main() {
+    ///     let gen = IdGenerator::builder().length(10).build().unwrap();
+    ///     let ids = gen.generate_n(10_000);
+    ///
+    ///     assert_eq!(ids.len(), 10_000);
+    ///     assert!(ids.iter().all(|id| id.len() == 10));
+    /// }
+    /// ```
+    pub fn generate_n(&self, n: usize) -> Vec<String> {
+        let mut ids = vec![String::new(); n];
+        self.fill(&mut ids);
+
+        ids
+    }
+
+    /// Overwrites every string in `ids` with a new ID, reusing their existing
+    /// allocations.
+    ///
+    /// Filling the same buffer repeatedly generates IDs without allocating once
+    /// every string has grown to the length of an ID. Otherwise this behaves the
+    /// same as [IdGenerator::generate_n], including panicking in the same way as
+    /// [IdGenerator::generate].
+    pub fn fill(&self, ids: &mut [String]) {
+        match BufferedRng::for_generator(self) {
+            Some(mut rng) => self.fill_with_rng(&mut rng, ids),
+            None => self.with_rng(|rng| self.fill_with_rng(rng, ids)),
+        }
+    }
+
+    /// Overwrites every string in `ids` with a new ID drawn from `rng`.
+    pub(crate) fn fill_with_rng<R: RngCore + ?Sized>(&self, rng: &mut R, ids: &mut [String]) {
+        let mut symbols = Vec::with_capacity(self.symbol_count());
+
+        for id in ids.iter_mut() {
+            self.write_id(rng, &mut symbols, id)
+                .expect("blocklist retries were exhausted");
+        }
+    }
+
+    /// Returns an infinite iterator of new IDs, drawing randomness in blocks in
+    /// the same way as [IdGenerator::generate_n].
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use randid::IdGenerator;
+    ///
+    /// fn main() {
+    ///     let gen = IdGenerator::builder().prefix("row_").build().unwrap();
+    ///
+    ///     for (row, id) in gen.iter().take(3).enumerate() {
+    ///         println!("INSERT INTO rows VALUES ({}, '{}');", row, id);
+    ///     }
+    /// }
+    /// ```
+    pub fn iter(&self) -> Ids<'_> {
+        Ids::new(self)
+    }
+
+    /// Overwrites `generated` with a new ID, using `symbols` as scratch space.
+    ///
+    /// Errors are the same as [IdGenerator::try_generate].
+    pub(crate) fn write_id<R: RngCore + ?Sized>(
+        &self,
+        rng: &mut R,
+        symbols: &mut Vec<char>,
+        generated: &mut String,
+    ) -> Result<(), RandidError> {
+        for _ in 0..=self.max_retries {
+            symbols.clear();
+            symbols.extend((0..self.length).map(|_| self.alphabet.sample(rng)));
+
+            if let Some(checksum) = self.checksum {
+                let payload: String = symbols.iter().collect();
+
+                symbols.push(
+                    checksum
+                        .check_symbol(&self.alphabet, &payload)
+                        .expect("generated symbols are valid for the checksum"),
+                );
+            }
+
+            match &self.blocklist {
+                Some(blocklist) if blocklist.is_blocked(&symbols.iter().collect::<String>()) => {
+                    self.rejected.fetch_add(1, Ordering::Relaxed);
+                }
+                _ => {
+                    self.assemble_into(symbols, generated);
+
+                    return Ok(());
+                }
+            }
+        }
+
+        self.exhausted.fetch_add(1, Ordering::Relaxed);
+
+        Err(RandidError::BlocklistExhausted {
+            attempts: self.max_retries + 1,
+        })
+    }
+
+    /// Joins the prefix, random `symbols` split into any configured groups and
+    /// the suffix into a final ID.
+    fn assemble(&self, symbols: &[char]) -> String {
+        let mut generated = String::new();
+        self.assemble_into(symbols, &mut generated);
+
+        generated
+    }
+
+    /// Overwrites `generated` with the assembled ID, see [IdGenerator::assemble].
+    fn assemble_into(&self, symbols: &[char], generated: &mut String) {
+        generated.clear();
+        generated.reserve(self.capacity());
+        generated.push_str(&self.prefix);
+
+        for (position, symbol) in symbols.iter().enumerate() {
+            if let Some((size, separator)) = self.group {
+                if position != 0 && position % size == 0 {
+                    generated.push(separator);
+                }
+            }
+
+            generated.push(*symbol);
+        }
+
+        generated.push_str(&self.suffix);
+    }
+
+    /// Alphabet which random symbols are drawn from.
+    pub fn alphabet(&self) -> &Alphabet {
+        &self.alphabet
+    }
+
+    /// Number of random symbols in each ID, not including the prefix, suffix or
+    /// group separators.
+    pub fn length(&self) -> usize {
+        self.length
+    }
+
+    /// Size and separator of the groups random symbols are split into, if any.
+    pub fn group(&self) -> Option<(usize, char)> {
+        self.group
+    }
+
+    /// Static text placed before the random symbols.
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    /// Static text placed after the random symbols.
+    pub fn suffix(&self) -> &str {
+        &self.suffix
+    }
+
+    /// Source of randomness used by [IdGenerator::generate].
+    pub fn rng(&self) -> RngSource {
+        self.rng
+    }
+
+    /// Entropy and collision estimates for the IDs of this generator, see the
+    /// [analysis](crate::analysis) module.
+    ///
+    /// Only the random symbols count towards the entropy, as the prefix, suffix,
+    /// groups and any check character are the same or derived for every ID. A
+    /// blocklist lowers the entropy slightly, which isn't accounted for.
+    pub fn analysis(&self) -> Analysis {
+        Analysis::new(self.alphabet.size(), self.length)
+    }
+
+    /// Checksum scheme whose check character is appended to each ID, if any.
+    pub fn checksum(&self) -> Option<Checksum> {
+        self.checksum
+    }
+
+    /// Returns `true` if `id` could have been generated by this generator.
+    ///
+    /// The prefix, suffix, group separators, length and alphabet are all checked,
+    /// along with the check character if a [Checksum] is configured, which
+    /// catches the typos documented for the scheme. This doesn't say whether `id`
+    /// was actually generated, so it's a cheap first check before looking an ID
+    /// up rather than a replacement for doing so.
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use randid::{Alphabet, Checksum, IdGenerator};
+    ///
+    /// fn main() {
+    ///     let gen = IdGenerator::builder()
+    ///         .alphabet(Alphabet::digits())
+    ///         .length(8)
+    ///         .checksum(Checksum::Damm)
+    ///         .build()
+    ///         .unwrap();
+    ///
+    ///     let id = gen.generate();
+    ///     let mut typo: Vec<char> = id.chars().collect();
+    ///     typo.swap(2, 3);
+    ///
+    ///     assert!(gen.validate(&id));
+    ///     assert_eq!(typo[2] == typo[3], gen.validate(&typo.into_iter().collect::<String>()));
+    /// }
+    /// ```
+    pub fn validate(&self, id: &str) -> bool {
+        let body = match id
+            .strip_prefix(self.prefix.as_str())
+            .and_then(|rest| rest.strip_suffix(self.suffix.as_str()))
+        {
+            Some(body) => body,
+            None => return false,
+        };
+        let symbols: String = match self.group {
+            Some((_, separator)) => body.chars().filter(|c| *c != separator).collect(),
+            None => body.to_string(),
+        };
+
+        if self.assemble(&symbols.chars().collect::<Vec<_>>()) != id
+            || symbols.chars().count() != self.symbol_count()
+        {
+            return false;
+        }
+
+        match self.checksum {
+            Some(checksum) => {
+                let payload_len = symbols
+                    .char_indices()
+                    .nth(self.length)
+                    .map_or(0, |(i, _)| i);
+
+                symbols[..payload_len]
+                    .chars()
+                    .all(|c| self.alphabet.contains(c))
+                    && checksum.validate(&self.alphabet, &symbols)
+            }
+            None => symbols.chars().all(|c| self.alphabet.contains(c)),
+        }
+    }
+
+    /// Number of symbols in each ID, including any check character.
+    fn symbol_count(&self) -> usize {
+        self.length + self.checksum.map_or(0, |_| 1)
+    }
+
+    /// Blocklist which the random symbols of each ID are checked against, if any.
+    pub fn blocklist(&self) -> Option<&Blocklist> {
+        self.blocklist.as_ref()
+    }
+
+    /// Counts of how often the blocklist has triggered since this generator was
+    /// built, which stay at zero without a blocklist.
+    pub fn blocklist_metrics(&self) -> BlocklistMetrics {
+        BlocklistMetrics {
+            rejected: self.rejected.load(Ordering::Relaxed),
+            exhausted: self.exhausted.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Runs `f` with the random number generator of the configured [RngSource].
+    pub(crate) fn with_rng<T>(&self, f: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        match &self.seeded {
+            Some(seeded) => {
+                let mut rng = seeded
+                    .lock()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+                f(&mut *rng)
+            }
+            None if self.rng == RngSource::Os => f(&mut OsRng),
+            None => f(&mut rand::thread_rng()),
+        }
+    }
+
+    /// Byte capacity to reserve for a generated [String].
+    fn capacity(&self) -> usize {
+        let max_char = match self.alphabet.is_ascii() {
+            true => 1,
+            false => self
+                .alphabet
+                .symbols()
+                .iter()
+                .map(|c| c.len_utf8())
+                .max()
+                .unwrap_or(1),
+        };
+
+        let separators = match self.group {
+            Some((size, separator)) => (self.symbol_count() - 1) / size * separator.len_utf8(),
+            None => 0,
+        };
+
+        self.prefix.len() + self.symbol_count() * max_char + separators + self.suffix.len()
+    }
+}
+
+impl Clone for IdGenerator {
+    fn clone(&self) -> Self {
+        let seeded = self.seeded.as_ref().map(|seeded| {
+            let rng = seeded
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            Mutex::new(rng.clone())
+        });
+
+        Self {
+            alphabet: self.alphabet.clone(),
+            length: self.length,
+            prefix: self.prefix.clone(),
+            suffix: self.suffix.clone(),
+            group: self.group,
+            checksum: self.checksum,
+            rng: self.rng,
+            seeded,
+            blocklist: self.blocklist.clone(),
+            max_retries: self.max_retries,
+            rejected: AtomicU64::new(self.rejected.load(Ordering::Relaxed)),
+            exhausted: AtomicU64::new(self.exhausted.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+/// Builder for an [IdGenerator], created using [IdGenerator::builder].
+#[derive(Debug, Clone)]
+pub struct IdGeneratorBuilder {
+    alphabet: Result<Alphabet, AlphabetError>,
+    length: usize,
+    max_length: usize,
+    prefix: String,
+    suffix: String,
+    group: Option<(usize, char)>,
+    checksum: Option<Checksum>,
+    rng: RngSource,
+    blocklist: Option<Blocklist>,
+    max_retries: usize,
+}
+
+impl Default for IdGeneratorBuilder {
+    fn default() -> Self {
+        Self {
+            alphabet: Ok(Alphabet::base62()),
+            length: 16,
+            max_length: DEFAULT_MAX_LENGTH,
+            prefix: String::new(),
+            suffix: String::new(),
+            group: None,
+            checksum: None,
+            rng: RngSource::default(),
+            blocklist: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+}
+
+impl IdGeneratorBuilder {
+    /// Sets the alphabet which random symbols are drawn from, defaulting to
+    /// [Alphabet::base62].
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = Ok(alphabet);
+        self
+    }
+
+    /// Sets the alphabet from the characters of `symbols`, validated in the same
+    /// way as [Alphabet::new].
+    ///
+    /// Any problems with the alphabet are reported by [IdGeneratorBuilder::build]
+    /// as [RandidError::InvalidAlphabet].
+    pub fn alphabet_str(mut self, symbols: &str) -> Self {
+        self.alphabet = Alphabet::new(symbols);
+        self
+    }
+
+    /// Sets the number of random symbols in each ID, defaulting to `16`.
+    pub fn length(mut self, length: usize) -> Self {
+        self.length = length;
+        self
+    }
+
+    /// Sets the maximum length allowed by [IdGeneratorBuilder::length], defaulting
+    /// to [DEFAULT_MAX_LENGTH].
+    ///
+    /// This guards against absurd allocations when lengths come from untrusted
+    /// input such as configuration files or query parameters.
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.max_length = max_length;
+        self
+    }
+
+    /// Sets static text placed before the random symbols of each ID.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Sets static text placed after the random symbols of each ID.
+    pub fn suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Splits the random symbols of each ID into groups of `size`, joined by
+    /// `separator`, such as `ABCD-EFGH` for a size of `4` and a separator of `-`.
+    ///
+    /// This makes long IDs easier to read out loud and type back in, see
+    /// [Alphabet::readable] and [normalize_readable](crate::normalize_readable).
+    /// The separator must not be a member of the alphabet, so grouped IDs can
+    /// always be split back into their symbols.
+    pub fn group(mut self, size: usize, separator: char) -> Self {
+        self.group = Some((size, separator));
+        self
+    }
+
+    /// Appends a check character computed by `checksum` to the random symbols of
+    /// each ID, so typos can be caught by [IdGenerator::validate].
+    ///
+    /// The check character is in addition to [IdGeneratorBuilder::length] and
+    /// counts towards any groups. Decimal schemes such as [Checksum::Damm] need
+    /// an alphabet of only digits, like [Alphabet::digits].
+    pub fn checksum(mut self, checksum: Checksum) -> Self {
+        self.checksum = Some(checksum);
+        self
+    }
+
+    /// Sets the source of randomness used by [IdGenerator::generate].
+    pub fn rng(mut self, rng: RngSource) -> Self {
+        self.rng = rng;
+        self
+    }
+
+    /// Sets a blocklist which the random symbols of each ID are checked against,
+    /// regenerating any ID containing a blocked word. The prefix and suffix aren't
+    /// checked.
+    ///
+    /// Without a blocklist, which is the default, generated IDs may contain
+    /// offensive words by chance, so public-facing IDs should usually use
+    /// [Blocklist::english].
+    ///
+    /// As rejected IDs are never produced, this slightly reduces the entropy of
+    /// each ID and means a seeded generator's sequence depends on the blocklist.
+    pub fn blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = Some(blocklist);
+        self
+    }
+
+    /// Sets how many times an ID containing a blocked word is regenerated before
+    /// giving up, defaulting to [DEFAULT_MAX_RETRIES].
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Builds the final [IdGenerator], validating its configuration.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::ZeroLength] or [RandidError::LengthExceeded] if the
+    /// length is `0` or above the maximum, or [RandidError::InvalidAlphabet] if an
+    /// alphabet given to [IdGeneratorBuilder::alphabet_str] was invalid. Groups of
+    /// `0` give [RandidError::ZeroGroupSize] and a group separator within the
+    /// alphabet gives [AlphabetError::Duplicate]. Decimal checksums with an
+    /// alphabet of anything other than digits give [AlphabetError::NonDigit], and
+    /// [Checksum::LuhnModN] with an odd number of symbols gives
+    /// [AlphabetError::OddSize].
+    pub fn build(self) -> Result<IdGenerator, RandidError> {
+        let alphabet = self.alphabet?;
+
+        if matches!(self.checksum, Some(checksum) if checksum.is_decimal()) {
+            if let Some(c) = alphabet.symbols().iter().find(|c| !c.is_ascii_digit()) {
+                return Err(AlphabetError::NonDigit(*c).into());
+            }
+        } else if self.checksum == Some(Checksum::LuhnModN) && alphabet.size() % 2 == 1 {
+            return Err(AlphabetError::OddSize {
+                size: alphabet.size(),
+            }
+            .into());
+        }
+
+        if let Some((size, separator)) = self.group {
+            if size == 0 {
+                return Err(RandidError::ZeroGroupSize);
+            } else if alphabet.contains(separator) {
+                return Err(AlphabetError::Duplicate(separator).into());
+            }
+        }
+
+        if self.length == 0 {
+            return Err(RandidError::ZeroLength);
+        } else if self.length > self.max_length {
+            return Err(RandidError::LengthExceeded {
+                length: self.length,
+                max: self.max_length,
+            });
+        }
+
+        let seeded = match self.rng {
+            RngSource::Seeded(seed) => Some(Mutex::new(ChaCha20Rng::from_seed(seed))),
+            _ => None,
+        };
+
+        Ok(IdGenerator {
+            alphabet,
+            length: self.length,
+            prefix: self.prefix,
+            suffix: self.suffix,
+            group: self.group,
+            checksum: self.checksum,
+            rng: self.rng,
+            seeded,
+            blocklist: self.blocklist.filter(|blocklist| !blocklist.is_empty()),
+            max_retries: self.max_retries,
+            rejected: AtomicU64::new(0),
+            exhausted: AtomicU64::new(0),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::normalize_readable;
+
+    /// Checks prefixes and suffixes wrap the random body of an id
+    #[test]
+    fn prefix_suffix() {
+        let gen = IdGenerator::builder()
+            .length(6)
+            .prefix("ord_")
+            .suffix(".json")
+            .build()
+            .unwrap();
+        let result = gen.generate();
+
+        assert!(result.starts_with("ord_"));
+        assert!(result.ends_with(".json"));
+        assert_eq!(4 + 6 + 5, result.len());
+    }
+
+    /// Checks the operating system source generates full ids
+    #[test]
+    fn os_rng() {
+        let gen = IdGenerator::builder()
+            .length(22)
+            .rng(RngSource::Os)
+            .build()
+            .unwrap();
+
+        assert_eq!(22, gen.generate().len());
+    }
+
+    /// Golden values for seeded generation which must never change between
+    /// versions, as documented on [IdGenerator::from_seed]
+    #[test]
+    fn seeded_golden() {
+        let gen = IdGenerator::from_seed([0; 32]);
+
+        assert_eq!("sW0JzWeBQHtugL3o", gen.generate());
+        assert_eq!("VLOpB8IofSLqnHiB", gen.generate());
+
+        let digits = IdGenerator::builder()
+            .alphabet(Alphabet::digits())
+            .length(12)
+            .rng(RngSource::Seeded([42; 32]))
+            .build()
+            .unwrap();
+
+        assert_eq!("854845456499", digits.generate());
+    }
+
+    /// Clones of a seeded generator continue from the same position
+    #[test]
+    fn seeded_clone() {
+        let gen = IdGenerator::from_seed([1; 32]);
+        gen.generate();
+        let cloned = gen.clone();
+
+        assert_eq!(gen.generate(), cloned.generate());
+    }
+
+    /// Checks only characters from a custom alphabet are used, including
+    /// multi-byte ones
+    #[test]
+    fn custom_alphabet() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::new("aé").unwrap())
+            .length(64)
+            .build()
+            .unwrap();
+        let result = gen.generate();
+
+        assert_eq!(64, result.chars().count());
+        assert!(result.chars().all(|c| c == 'a' || c == 'é'));
+    }
+
+    /// Checks random symbols are split into groups, with no trailing separator
+    #[test]
+    fn grouped() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::readable())
+            .length(10)
+            .group(4, '-')
+            .prefix("T")
+            .build()
+            .unwrap();
+        let result = gen.generate();
+        let groups: Vec<&str> = result[1..].split('-').collect();
+
+        assert_eq!(
+            vec![4, 4, 2],
+            groups.iter().map(|g| g.len()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            result[1..].replace('-', ""),
+            normalize_readable(&result[1..]).unwrap()
+        );
+        assert_eq!(
+            Some(RandidError::InvalidAlphabet(AlphabetError::Duplicate('A'))),
+            IdGenerator::builder()
+                .alphabet(Alphabet::readable())
+                .group(4, 'A')
+                .build()
+                .err()
+        );
+    }
+
+    /// Checks the analysis only counts random symbols
+    #[test]
+    fn analysis() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::digits())
+            .length(12)
+            .prefix("n")
+            .checksum(Checksum::Damm)
+            .build()
+            .unwrap();
+
+        assert_eq!(Analysis::new(10, 12), gen.analysis());
+    }
+
+    /// Checks generated ids validate and typos, wrong prefixes and wrong lengths
+    /// don't
+    #[test]
+    fn checksum() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::readable())
+            .length(7)
+            .group(4, '-')
+            .prefix("T")
+            .checksum(Checksum::LuhnModN)
+            .build()
+            .unwrap();
+
+        for _ in 0..100 {
+            let id = gen.generate();
+            let mut typo: Vec<char> = id.chars().collect();
+            typo[1] = if typo[1] == 'A' { 'B' } else { 'A' };
+
+            assert_eq!(10, id.len());
+            assert!(gen.validate(&id));
+            assert!(!gen.validate(&typo.into_iter().collect::<String>()));
+            assert!(!gen.validate(&id[1..]));
+            assert!(!gen.validate(&id.replace('-', "")));
+            assert!(!gen.validate(&format!("{}A", id)));
+        }
+
+        assert_eq!(
+            Some(RandidError::InvalidAlphabet(AlphabetError::NonDigit('A'))),
+            IdGenerator::builder()
+                .checksum(Checksum::Luhn)
+                .build()
+                .err()
+        );
+        assert_eq!(
+            Some(RandidError::InvalidAlphabet(AlphabetError::OddSize {
+                size: 5
+            })),
+            IdGenerator::builder()
+                .alphabet_str("abcde")
+                .checksum(Checksum::LuhnModN)
+                .build()
+                .err()
+        );
+    }
+
+    /// Checks blocked words are regenerated and counted, without checking the
+    /// prefix
+    #[test]
+    fn blocklist() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::new("ab").unwrap())
+            .length(2)
+            .prefix("bb_")
+            .blocklist(Blocklist::new(vec!["BB", "aa", "ab"]))
+            .max_retries(1_000)
+            .build()
+            .unwrap();
+
+        for _ in 0..20 {
+            assert_eq!("bb_ba", gen.try_generate().unwrap());
+        }
+
+        let metrics = gen.blocklist_metrics();
+
+        assert!(metrics.rejected > 0);
+        assert_eq!(0, metrics.exhausted);
+    }
+
+    /// Checks an unsatisfiable blocklist errors once retries run out
+    #[test]
+    fn blocklist_exhausted() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::new("ab").unwrap())
+            .blocklist(Blocklist::new(vec!["a", "b"]))
+            .max_retries(3)
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Err(RandidError::BlocklistExhausted { attempts: 4 }),
+            gen.try_generate()
+        );
+        assert_eq!(
+            BlocklistMetrics {
+                rejected: 4,
+                exhausted: 1
+            },
+            gen.blocklist_metrics()
+        );
+    }
+
+    /// Checks invalid configurations are rejected when building
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            Some(RandidError::ZeroLength),
+            IdGenerator::builder().length(0).build().err()
+        );
+        assert_eq!(
+            Some(RandidError::LengthExceeded {
+                length: 11,
+                max: 10
+            }),
+            IdGenerator::builder()
+                .length(11)
+                .max_length(10)
+                .build()
+                .err()
+        );
+        assert_eq!(
+            Some(RandidError::InvalidAlphabet(AlphabetError::Duplicate('a'))),
+            IdGenerator::builder().alphabet_str("aba").build().err()
+        );
+        assert_eq!(
+            Some(RandidError::ZeroGroupSize),
+            IdGenerator::builder().group(0, '-').build().err()
+        );
+    }
+}
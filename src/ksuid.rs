@@ -0,0 +1,195 @@
+//! K-sortable unique IDs compatible with
+//! [segmentio/ksuid](https://github.com/segmentio/ksuid), see [Ksuid].
+
+use crate::base62;
+use crate::clock;
+use crate::RandidError;
+use rand::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Custom epoch of KSUID timestamps, `2014-05-13T16:53:20Z` in seconds since the
+/// Unix epoch.
+pub const KSUID_EPOCH: u64 = 1_400_000_000;
+
+/// A 160-bit K-sortable unique identifier.
+///
+/// Each KSUID is made of a 32-bit timestamp, counted in seconds since
+/// [KSUID_EPOCH], followed by a 128-bit random payload. They're rendered as 27
+/// characters of BASE62 using the same alphabet as
+/// [randid_string](crate::randid_string), like `0ujtsYcgvSTl8PAuAdqWYSMnLOv`, and
+/// both the KSUIDs and their string forms sort by the time they were generated.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::Ksuid;
+///
+/// fn main() {
+///     let id = Ksuid::new();
+///     let parsed: Ksuid = id.to_string().parse().unwrap();
+///
+///     assert_eq!(id, parsed);
+///     assert_eq!(id.to_string().len(), 27);
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Ksuid([u8; 20]);
+
+impl Ksuid {
+    /// Length of the string form of a KSUID.
+    pub const LEN: usize = 27;
+
+    /// Generates a KSUID for the current time using [rand::thread_rng].
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a KSUID for the current time using the provided random number
+    /// generator.
+    pub fn with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self::at(clock::now_millis() / 1000, rng)
+    }
+
+    /// Generates a KSUID for the given number of seconds since the Unix epoch,
+    /// clamped to the range a KSUID timestamp can hold.
+    pub fn at<R: RngCore + ?Sized>(unix_secs: u64, rng: &mut R) -> Self {
+        let timestamp = unix_secs.saturating_sub(KSUID_EPOCH).min(u32::MAX as u64);
+        let mut payload = [0; 16];
+        rng.fill_bytes(&mut payload);
+
+        Self::from_parts(timestamp as u32, payload)
+    }
+
+    /// Creates a KSUID from a timestamp, in seconds since [KSUID_EPOCH], and a
+    /// payload.
+    pub fn from_parts(timestamp: u32, payload: [u8; 16]) -> Self {
+        let mut bytes = [0; 20];
+        bytes[..4].copy_from_slice(&timestamp.to_be_bytes());
+        bytes[4..].copy_from_slice(&payload);
+
+        Self(bytes)
+    }
+
+    /// Creates a KSUID from its raw big-endian bytes.
+    pub const fn from_bytes(bytes: [u8; 20]) -> Self {
+        Self(bytes)
+    }
+
+    /// Raw big-endian bytes of this KSUID.
+    pub fn as_bytes(&self) -> &[u8; 20] {
+        &self.0
+    }
+
+    /// Timestamp of this KSUID, in seconds since [KSUID_EPOCH].
+    pub fn timestamp(&self) -> u32 {
+        let mut timestamp = [0; 4];
+        timestamp.copy_from_slice(&self.0[..4]);
+
+        u32::from_be_bytes(timestamp)
+    }
+
+    /// Timestamp of this KSUID, in seconds since the Unix epoch.
+    pub fn unix_timestamp(&self) -> u64 {
+        self.timestamp() as u64 + KSUID_EPOCH
+    }
+
+    /// The 128-bit random payload of this KSUID.
+    pub fn payload(&self) -> &[u8] {
+        &self.0[4..]
+    }
+}
+
+impl fmt::Display for Ksuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", base62::encode_bytes_padded(&self.0, Ksuid::LEN))
+    }
+}
+
+impl FromStr for Ksuid {
+    type Err = RandidError;
+
+    /// Parses the 27 character BASE62 form of a KSUID.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found = s.chars().count();
+
+        if found != Ksuid::LEN {
+            return Err(RandidError::InvalidLength {
+                expected: Ksuid::LEN,
+                found,
+            });
+        }
+
+        let mut bytes = [0; 20];
+        bytes.copy_from_slice(&base62::decode_bytes_padded(s, 20)?);
+
+        Ok(Self(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks parsing and formatting against the example of segmentio/ksuid
+    #[test]
+    fn reference_example() {
+        let ksuid: Ksuid = "0ujtsYcgvSTl8PAuAdqWYSMnLOv".parse().unwrap();
+
+        assert_eq!(107608047, ksuid.timestamp());
+        assert_eq!(1507608047, ksuid.unix_timestamp());
+        assert_eq!(
+            [
+                0xB5, 0xA1, 0xCD, 0x34, 0xB5, 0xF9, 0x9D, 0x11, 0x54, 0xFB, 0x68, 0x53, 0x34, 0x5C,
+                0x97, 0x35
+            ],
+            ksuid.payload()
+        );
+        assert_eq!("0ujtsYcgvSTl8PAuAdqWYSMnLOv", ksuid.to_string());
+    }
+
+    /// Checks the smallest and largest KSUIDs encode to fixed-width strings
+    #[test]
+    fn bounds() {
+        let max = Ksuid::from_bytes([0xff; 20]);
+
+        assert_eq!("000000000000000000000000000", Ksuid::default().to_string());
+        assert_eq!("aWgEPTl1tmebfsQzFP4bxwgy80V", max.to_string());
+        assert_eq!(max, max.to_string().parse().unwrap());
+    }
+
+    /// Checks KSUIDs and their strings sort by timestamp
+    #[test]
+    fn ordering() {
+        let mut rng = rand::thread_rng();
+        let earlier = Ksuid::at(KSUID_EPOCH + 10, &mut rng);
+        let later = Ksuid::at(KSUID_EPOCH + 11, &mut rng);
+
+        assert!(earlier < later);
+        assert!(earlier.to_string() < later.to_string());
+        assert_eq!(10, earlier.timestamp());
+    }
+
+    /// Checks malformed text is rejected, including values above 160 bits
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(RandidError::InvalidLength {
+                expected: 27,
+                found: 3
+            }),
+            "abc".parse::<Ksuid>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '-',
+                position: 1
+            }),
+            "0-jtsYcgvSTl8PAuAdqWYSMnLOv".parse::<Ksuid>()
+        );
+        assert_eq!(
+            Err(RandidError::Overflow),
+            "zzzzzzzzzzzzzzzzzzzzzzzzzzz".parse::<Ksuid>()
+        );
+    }
+}
@@ -1,18 +1,158 @@
 //! Randid (pronounced like random but with `-id` instead of `-om`) is a minimalistic,
 //! web-safe library for generating customisable IDs for use in primarily web
-//! applications. The generated IDs are not guarenteed to be unique however.
+//! applications. The generated IDs are not guarenteed to be unique however, unless
+//! generated through a [UniqueGenerator].
 //!
 //! ## Common functions
 //!
-//! | Overview                                 | Function signature   | Example call + response      |
-//! |------------------------------------------|----------------------|------------------------------|
-//! | Random BASE62 string of exact length     | randid_str(len: i32) | `randid_str(5)` -> `"bWk9D"` |
-//! | Random padded i32 string of exact length | randid_i32(len: i32) | `randid_int(5)` -> `"00396"` |
+//! | Overview                                 | Function signature            | Example call + response             |
+//! |------------------------------------------|-------------------------------|-------------------------------------|
+//! | Random BASE62 string of exact length     | randid_string(len: usize)     | `randid_string(5)` -> `Ok("bWk9D")` |
+//! | Random padded digit string of any length | randid_digits(len: usize)     | `randid_digits(5)` -> `Ok("90396")` |
+//! | Random integer of exact digit count      | randid_u64(digits: usize)     | `randid_u64(5)` -> `Ok(48213)`      |
+//! | Secure random BASE62 string              | randid_secure_str(len: usize) | `randid_secure_str(5)` -> `Ok(..)`  |
+//! | Nano ID-compatible string of 21 symbols  | randid_nanoid()               | `randid_nanoid()` -> `"V1StGX.."`   |
+//!
+//! Each of these return a [RandidError] for lengths of `0` or above
+//! [DEFAULT_MAX_LENGTH]. The original `randid_str(len: i32)` and
+//! `randid_i32(len: i32)` functions are still available but deprecated.
+//!
+//! On hot paths where the length is fixed, [RandId] generates the same BASE62
+//! IDs inline on the stack without allocating a [String]. When generating
+//! thousands or millions of IDs at once, such as to seed a database, build an
+//! [IdGenerator] once and use [IdGenerator::generate_n], [IdGenerator::fill] or
+//! [IdGenerator::iter], which draw randomness in large blocks instead of once per
+//! call.
+//!
+//! ## Security
+//!
+//! [randid_string] and [randid_digits] use [rand::thread_rng], which is fast and
+//! currently backed by a cryptographically secure generator, but this isn't a
+//! guarantee made by randid or `rand` and may change between versions.
+//!
+//! When IDs act as secrets, such as password-reset tokens, session handles or
+//! unguessable share links, use [randid_secure_str] instead which draws directly
+//! from the operating system's CSPRNG. This protects against an attacker who can
+//! observe any number of previously issued IDs and wishes to predict past or future
+//! ones, provided the ID is long enough to resist guessing (at least 22 BASE62
+//! characters gives over 128 bits of entropy). It doesn't protect against an
+//! attacker who can read the memory of the generating process or tamper with the
+//! operating system's entropy source. [randid_str_with_rng] only accepts generators
+//! implementing [CryptoRng] so the same guarantee is enforced at the type level
+//! when bringing your own generator.
+//!
+//! ## Custom generators
+//!
+//! All of the functions above are thin wrappers over an [IdGenerator], which can
+//! be configured with a custom [Alphabet], length, prefix/suffix and source of
+//! randomness using [IdGenerator::builder]. Seeded generators created with
+//! [IdGenerator::from_seed] produce a stable sequence of IDs for reproducible tests.
+//! To choose a length, the [analysis] module estimates the entropy and collision
+//! risk of a configuration, or recommends a length for a target volume of IDs.
+//!
+//! Random IDs can contain offensive words by chance, which matters when they
+//! appear in public-facing URLs. Giving a generator a [Blocklist], such as the
+//! built-in [Blocklist::english], regenerates any ID containing a blocked word.
+//!
+//! For IDs which are read over the phone or typed in by hand, use
+//! [Alphabet::readable] with [IdGeneratorBuilder::group] to give IDs like
+//! `7KQM-3XZA`, then [normalize_readable] to parse what users enter.
+//! Adding a [Checksum] with [IdGeneratorBuilder::checksum] appends a check
+//! character so [IdGenerator::validate] can catch typos before a lookup.
+//!
+//! To tag IDs with the kind of entity they identify, like `user_2x4Bf9qTz7LmR0aK`,
+//! declare a kind with [prefixed_id!] and use [PrefixedId], which also stops IDs of
+//! one kind being passed where another is expected.
+//!
+//! To guarantee IDs never repeat, wrap a generator in a [UniqueGenerator]. For
+//! batch jobs issuing hundreds of millions of IDs, a [BloomFilter] sized with
+//! [BloomFilter::with_false_positive_rate] keeps memory fixed where a `HashSet`
+//! would not, and can be saved to disk and loaded again between runs.
+//!
+//! ## Features
+//!
+//! - `serde`: implements `Serialize` and `Deserialize` for [Uuid], [Ulid],
+//!   [Ksuid], [Snowflake], [PrefixedId] and [RandId] using their string forms,
+//!   validating them on deserialization. Snowflakes are plain integers in
+//!   compact binary formats such as bincode.
+//! - `rayon`: adds [IdGenerator::par_iter] and [IdGenerator::par_generate_n] to
+//!   generate IDs across threads, reproducibly when seeded. Current releases of
+//!   rayon need Rust 1.80, above the 1.62 needed by the rest of randid, so older
+//!   compilers must lock an older rayon with `cargo update -p rayon --precise`.
+//!
+//! ## Standard formats
+//!
+//! Alongside randid's own IDs, standard [Uuid]s of version 4 (random) and version
+//! 7 (time-ordered) can be generated and rendered as hyphenated, simple or BASE62
+//! strings. Lexicographically sortable [Ulid]s are also available, with a
+//! [UlidGenerator] for strictly increasing ULIDs within the same millisecond, as
+//! well as [Ksuid]s which share randid's BASE62 alphabet.
+//!
+//! Existing integers and byte strings, such as database keys, can be shortened
+//! into the same url-safe form as [randid_string] using the [base62] module.
+//! For 64-bit integer IDs across sharded workers, see [SnowflakeGenerator].
+//! To hide sequential integers behind short, reversible strings instead, see
+//! [Obfuscator].
+
+use rand::rngs::OsRng;
+use rand::{CryptoRng, RngCore};
+
+mod alphabet;
+pub mod analysis;
+pub mod base62;
+mod batch;
+mod blocklist;
+mod bloom;
+mod checksum;
+mod clock;
+mod error;
+mod fixed;
+mod generator;
+mod ksuid;
+mod nanoid;
+mod numeric;
+mod obfuscate;
+#[cfg(feature = "rayon")]
+mod parallel;
+mod prefixed;
+mod readable;
+#[cfg(feature = "serde")]
+mod serde_impls;
+mod snowflake;
+mod ulid;
+mod unique;
+mod uuid;
+
+pub use alphabet::{Alphabet, AlphabetError};
+pub use batch::Ids;
+pub use blocklist::{Blocklist, BlocklistMetrics};
+pub use bloom::BloomFilter;
+pub use checksum::Checksum;
+pub use error::RandidError;
+pub use fixed::RandId;
+pub use generator::{
+    IdGenerator, IdGeneratorBuilder, RngSource, DEFAULT_MAX_LENGTH, DEFAULT_MAX_RETRIES,
+};
+pub use ksuid::{Ksuid, KSUID_EPOCH};
+pub use nanoid::{
+    nanoid_custom_random, nanoid_with_rng, randid_nanoid, NANOID_ALPHABET, NANOID_DEFAULT_SIZE,
+};
+pub use numeric::{randid_digits, randid_u128, randid_u32, randid_u64};
+pub use obfuscate::{Obfuscator, ObfuscatorBuilder};
+pub use prefixed::{IdKind, PrefixedId};
+pub use readable::normalize_readable;
+pub use snowflake::{
+    Snowflake, SnowflakeBuilder, SnowflakeGenerator, SnowflakeParts, DEFAULT_SNOWFLAKE_EPOCH,
+};
+pub use ulid::{Ulid, UlidGenerator};
+pub use unique::{UniqueError, UniqueGenerator, UniquenessStore};
+pub use uuid::Uuid;
 
-use rand::{self, Rng};
+/// The 62 characters used by BASE62, in ascending order
+const BASE62: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
 
-/// Array of
-const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+/// The 10 decimal digits used by [randid_digits], in ascending order
+const DIGITS: &str = "0123456789";
 
 /// Generates a random BASE62 [String] of a given length.
 ///
@@ -24,38 +164,121 @@ const BASE62: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrst
 /// [BASE64](https://en.wikipedia.org/wiki/Base64) due to the high likelyhood of
 /// this function being used for URLs.
 ///
+/// When the length is known at compile time, [RandId] gives the same IDs without
+/// allocating, converting into a [String] only when needed.
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] if `len` is `0`, or
+/// [RandidError::LengthExceeded] if it's above [DEFAULT_MAX_LENGTH].
+///
 /// ## Examples
 ///
 /// ```rust
-/// use randid::randid_str;
+/// use randid::randid_string;
 ///
 /// fn main() {
-///     let my_id = randid_str(5);
+///     let my_id = randid_string(5).unwrap();
 ///
 ///     println!("https://example.com/safeid/{}", my_id); // will provide a url-safe id like `bWk9D`, `yWvm3` or `POf3R`
 /// }
 /// ```
-pub fn randid_str(len: i32) -> String {
-    let mut generated = String::with_capacity(len as usize);
+pub fn randid_string(len: usize) -> Result<String, RandidError> {
+    IdGenerator::builder()
+        .length(len)
+        .build()
+        .map(|gen| gen.generate())
+}
 
-    let mut rng = rand::thread_rng();
+/// Generates a random BASE62 [String] of a given length using the operating
+/// system's cryptographically secure random number generator.
+///
+/// This should be used instead of [randid_string] whenever the ID is a secret, see
+/// the [crate-level security notes](crate#security) for the threat model. Errors
+/// are the same as [randid_string].
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::randid_secure_str;
+///
+/// fn main() {
+///     let reset_token = randid_secure_str(32).unwrap();
+///
+///     println!("https://example.com/reset/{}", reset_token);
+/// }
+/// ```
+pub fn randid_secure_str(len: usize) -> Result<String, RandidError> {
+    randid_str_with_rng(&mut OsRng, len)
+}
 
-    for _ in 0..len {
-        generated.push(BASE62[rng.gen::<usize>() % 62] as char);
-    }
+/// Generates a random BASE62 [String] of a given length using the provided
+/// cryptographically secure random number generator.
+///
+/// The [CryptoRng] bound ensures only generators suitable for secrets can be
+/// passed, see the [crate-level security notes](crate#security). Errors are the
+/// same as [randid_string].
+///
+/// ## Examples
+///
+/// ```rust
+/// use rand::rngs::OsRng;
+/// use randid::randid_str_with_rng;
+///
+/// fn main() {
+///     let session = randid_str_with_rng(&mut OsRng, 24).unwrap();
+///
+///     assert_eq!(session.len(), 24);
+/// }
+/// ```
+pub fn randid_str_with_rng<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    len: usize,
+) -> Result<String, RandidError> {
+    IdGenerator::builder()
+        .length(len)
+        .build()
+        .map(|gen| gen.generate_with_rng(rng))
+}
 
-    generated
+/// Generates a random BASE62 [String] of a given length.
+///
+/// Negative or zero lengths give an empty [String] and lengths aren't capped, use
+/// [randid_string] for a length which is checked.
+///
+/// ## Examples
+///
+/// ```rust
+/// # #![allow(deprecated)]
+/// use randid::randid_str;
+///
+/// fn main() {
+///     let my_id = randid_str(5);
+///
+///     println!("https://example.com/safeid/{}", my_id); // will provide a url-safe id like `bWk9D`, `yWvm3` or `POf3R`
+/// }
+/// ```
+#[deprecated(
+    since = "0.2.0",
+    note = "use `randid_string`, which takes a `usize` length and returns a `Result`"
+)]
+pub fn randid_str(len: i32) -> String {
+    legacy_generate(Alphabet::base62(), len)
 }
 
 /// Generates a random padded [i32]-based [String] according to the length.
 ///
+/// Despite the name, lengths above `9` can't be parsed back into an [i32]; use
+/// [randid_u32], [randid_u64] or [randid_u128] for real integers instead.
+///
 /// This function automatically finds the minimum and maximum integer for the given
 /// length. For example, if you input a length of `4` you can get anything between
-/// `"0000"` and `"9999"`.
+/// `"0000"` and `"9999"`. Negative or zero lengths give an empty [String].
 ///
 /// # Examples
 ///
 /// ```rust
+/// # #![allow(deprecated)]
 /// use randid::randid_i32;
 ///
 /// fn main() {
@@ -69,16 +292,24 @@ pub fn randid_str(len: i32) -> String {
 ///     );
 /// }
 /// ```
+#[deprecated(
+    since = "0.2.0",
+    note = "use `randid_digits`, which takes a `usize` length and returns a `Result`"
+)]
 pub fn randid_i32(len: i32) -> String {
-    let mut generated = String::with_capacity(len as usize);
-
-    for _ in 0..len {
-        let num = rand::thread_rng().gen_range(0, 9);
-
-        generated.push_str(&num.to_string()); // NOTE: probably not most efficiant
-    }
+    legacy_generate(Alphabet::digits(), len)
+}
 
-    generated
+/// Keeps the original behaviour of the [i32]-length functions, giving an empty
+/// [String] for lengths below one and no maximum.
+fn legacy_generate(alphabet: Alphabet, len: i32) -> String {
+    IdGenerator::builder()
+        .alphabet(alphabet)
+        .length(len.max(0) as usize)
+        .max_length(usize::MAX)
+        .build()
+        .map(|gen| gen.generate())
+        .unwrap_or_default()
 }
 
 #[cfg(test)]
@@ -87,15 +318,131 @@ mod tests {
 
     /// String length test for [randid_str]
     #[test]
+    #[allow(deprecated)]
     fn rand_str_len() {
         let result: String = randid_str(10);
 
         assert_eq!(10, result.len());
     }
 
+    /// String length and error test for [randid_string]
+    #[test]
+    fn rand_string_len() {
+        assert_eq!(10, randid_string(10).unwrap().len());
+        assert_eq!(Err(RandidError::ZeroLength), randid_string(0));
+        assert_eq!(
+            Err(RandidError::LengthExceeded {
+                length: DEFAULT_MAX_LENGTH + 1,
+                max: DEFAULT_MAX_LENGTH
+            }),
+            randid_string(DEFAULT_MAX_LENGTH + 1)
+        );
+    }
+
+    /// Checks the deprecated [i32] functions treat negative lengths as empty
+    /// instead of panicking
+    #[test]
+    #[allow(deprecated)]
+    fn rand_legacy_negative() {
+        assert_eq!("", randid_str(-5));
+        assert_eq!("", randid_i32(-5));
+    }
+
+    /// Upper critical values of the chi-squared distribution at a significance
+    /// of `1e-6`, keyed by degrees of freedom, so a correct sampler fails these
+    /// tests roughly once in a million runs
+    const CHI_SQUARED_CRITICAL: &[(usize, f64)] =
+        &[(2, 27.63), (9, 44.81), (32, 85.23), (61, 128.52)];
+
+    /// Looks up the critical value for `df` degrees of freedom
+    fn chi_squared_critical(df: usize) -> f64 {
+        CHI_SQUARED_CRITICAL
+            .iter()
+            .find(|(known, _)| *known == df)
+            .unwrap()
+            .1
+    }
+
+    /// Pearson's chi-squared statistic of `counts` against a uniform distribution
+    fn chi_squared(counts: &[u64]) -> f64 {
+        let total: u64 = counts.iter().sum();
+        let expected = total as f64 / counts.len() as f64;
+
+        counts
+            .iter()
+            .map(|&observed| (observed as f64 - expected).powi(2) / expected)
+            .sum()
+    }
+
+    /// Draws `draws` symbols from `alphabet` and checks they are uniformly
+    /// distributed
+    fn assert_uniform(alphabet: Alphabet, draws: usize) {
+        let gen = IdGenerator::builder()
+            .alphabet(alphabet.clone())
+            .length(draws)
+            .max_length(draws)
+            .build()
+            .unwrap();
+        let mut counts = vec![0; alphabet.size()];
+
+        for c in gen.generate().chars() {
+            counts[alphabet.index_of(c).unwrap()] += 1;
+        }
+
+        let critical = chi_squared_critical(alphabet.size() - 1);
+        let statistic = chi_squared(&counts);
+
+        assert!(
+            statistic < critical,
+            "chi-squared of {} exceeds {} for counts {:?}",
+            statistic,
+            critical,
+            counts
+        );
+    }
+
+    /// Chi-squared uniformity test for [randid_str] over millions of draws
+    #[test]
+    fn rand_str_uniform() {
+        let mut counts = vec![0; 62];
+
+        for _ in 0..3_100 {
+            for c in randid_string(1_000).unwrap().chars() {
+                counts[BASE62.find(c).unwrap()] += 1;
+            }
+        }
+
+        assert!(chi_squared(&counts) < chi_squared_critical(61));
+    }
+
+    /// Chi-squared uniformity test for alphabet sizes which aren't powers of two,
+    /// including `33` which is just above one and so rejects the most draws
+    #[test]
+    fn rand_alphabet_uniform() {
+        assert_uniform(Alphabet::new("abc").unwrap(), 1_000_000);
+        assert_uniform(Alphabet::digits(), 1_000_000);
+        assert_uniform(
+            Alphabet::new("abcdefghijklmnopqrstuvwxyzABCDEFG").unwrap(),
+            2_000_000,
+        );
+    }
+
+    /// String length test for [randid_secure_str] and [randid_str_with_rng]
+    #[test]
+    fn rand_secure_str_len() {
+        assert_eq!(32, randid_secure_str(32).unwrap().len());
+        assert_eq!(
+            7,
+            randid_str_with_rng(&mut rand::rngs::OsRng, 7)
+                .unwrap()
+                .len()
+        );
+    }
+
     /// Checks the number given by the [randid_i32] is within the correct range
     /// asked for
     #[test]
+    #[allow(deprecated)]
     fn rand_int_range() {
         let (min, max) = (0, 99999999);
 
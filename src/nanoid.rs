@@ -0,0 +1,239 @@
+//! IDs compatible with [Nano ID](https://github.com/ai/nanoid), see
+//! [randid_nanoid].
+
+use crate::{Alphabet, AlphabetError, RandidError};
+use rand::RngCore;
+
+/// The url-safe alphabet of Nano ID, made of `A-Za-z0-9_-` in the same order as
+/// the reference implementation.
+pub const NANOID_ALPHABET: &str =
+    "useandom-26T198340PX75pxJACKVERYMINDBUSHWOLF_GQZbfghjklqvwyzrict";
+
+/// Default number of symbols in a Nano ID, giving a similar collision probability
+/// to a version 4 UUID.
+pub const NANOID_DEFAULT_SIZE: usize = 21;
+
+/// Largest alphabet usable with Nano ID's masking algorithm, as each symbol is
+/// drawn from a single random byte.
+const MAX_ALPHABET_SIZE: usize = 256;
+
+/// Generates a Nano ID of the default size of `21` using Nano ID's url-safe
+/// alphabet, matching `nanoid()` from the reference implementation.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::randid_nanoid;
+///
+/// fn main() {
+///     let id = randid_nanoid();
+///
+///     println!("https://example.com/item/{}", id); // like `V1StGXR8_Z5jdHi6B-myT`
+/// }
+/// ```
+pub fn randid_nanoid() -> String {
+    nanoid_with_rng(
+        &mut rand::thread_rng(),
+        &Alphabet::nanoid(),
+        NANOID_DEFAULT_SIZE,
+    )
+    .expect("default nano id configuration is valid")
+}
+
+/// Generates a Nano ID of `size` symbols from `alphabet` using the provided random
+/// number generator, matching `customAlphabet` from the reference
+/// implementation.
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] if `size` is `0`, or
+/// [RandidError::InvalidAlphabet] if `alphabet` has more than 256 symbols.
+pub fn nanoid_with_rng<R: RngCore + ?Sized>(
+    rng: &mut R,
+    alphabet: &Alphabet,
+    size: usize,
+) -> Result<String, RandidError> {
+    nanoid_custom_random(alphabet, size, |step| {
+        let mut bytes = vec![0; step];
+        rng.fill_bytes(&mut bytes);
+        bytes
+    })
+}
+
+/// Generates a Nano ID of `size` symbols from `alphabet`, asking `random` for the
+/// given number of random bytes whenever more are needed, matching
+/// `customRandom` from the reference implementation.
+///
+/// Each byte is masked down to the smallest power of two covering the alphabet
+/// and rejected if it lands outside of it, so every symbol is equally likely.
+/// Bytes are requested in steps sized so that one step usually suffices, and each
+/// step is read from its last byte to its first. As the
+/// algorithm is the same as the reference implementation, the same bytes give the
+/// same ID, which is useful for cross-checking the two.
+///
+/// Errors are the same as [nanoid_with_rng].
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{nanoid_custom_random, Alphabet};
+///
+/// fn main() {
+///     let alphabet = Alphabet::new("abcde").unwrap();
+///     let sequence = [2, 255, 3, 7, 7, 7, 7, 7, 0, 1];
+///
+///     let fake_random = |step| sequence.iter().cycle().take(step).copied().collect();
+///
+///     assert_eq!(nanoid_custom_random(&alphabet, 4, fake_random).unwrap(), "adca");
+///     assert_eq!(
+///         nanoid_custom_random(&alphabet, 18, fake_random).unwrap(),
+///         "cbadcbadcbadcbadcc"
+///     );
+/// }
+/// ```
+pub fn nanoid_custom_random(
+    alphabet: &Alphabet,
+    size: usize,
+    mut random: impl FnMut(usize) -> Vec<u8>,
+) -> Result<String, RandidError> {
+    let symbols = alphabet.symbols();
+
+    if size == 0 {
+        return Err(RandidError::ZeroLength);
+    } else if symbols.len() > MAX_ALPHABET_SIZE {
+        return Err(AlphabetError::TooLarge {
+            size: symbols.len(),
+            max: MAX_ALPHABET_SIZE,
+        }
+        .into());
+    }
+
+    let mask = (2 << (31 - ((symbols.len() as u32 - 1) | 1).leading_zeros())) - 1;
+    // the reference uses `-~x`, which is one above the floor even for whole numbers
+    let step = (1.6 * mask as f64 * size as f64 / symbols.len() as f64).floor() as usize + 1;
+    let mut id = String::with_capacity(size);
+    let mut generated = 0;
+
+    loop {
+        let bytes = random(step);
+
+        // missing bytes are skipped, like `undefined` indices in the reference
+        for byte in (0..step).rev().filter_map(|j| bytes.get(j)) {
+            if let Some(symbol) = symbols.get((*byte as u32 & mask) as usize) {
+                id.push(*symbol);
+                generated += 1;
+
+                if generated == size {
+                    return Ok(id);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Byte sequence used by the `customRandom` tests of the reference
+    /// implementation
+    const SEQUENCE: [u8; 10] = [2, 255, 3, 7, 7, 7, 7, 7, 0, 1];
+
+    /// Fake random source repeating [SEQUENCE], as in the reference tests
+    fn fake_random(step: usize) -> Vec<u8> {
+        SEQUENCE.iter().cycle().take(step).copied().collect()
+    }
+
+    /// Cross-checks against the published `customRandom` vectors of Nano ID
+    #[test]
+    fn reference_vector() {
+        let alphabet = Alphabet::new("abcde").unwrap();
+
+        assert_eq!(
+            "adca",
+            nanoid_custom_random(&alphabet, 4, fake_random).unwrap()
+        );
+        assert_eq!(
+            "cbadcbadcbadcbadcc",
+            nanoid_custom_random(&alphabet, 18, fake_random).unwrap()
+        );
+    }
+
+    /// Checks steps are sized as `-~x` in the reference, including when `x` is
+    /// a whole number
+    #[test]
+    fn step_size() {
+        let alphabet = Alphabet::new("ab").unwrap();
+        let mut steps = Vec::new();
+
+        nanoid_custom_random(&alphabet, 5, |step| {
+            steps.push(step);
+            vec![0; step]
+        })
+        .unwrap();
+
+        assert_eq!(vec![5], steps);
+    }
+
+    /// Checks the default alphabet is `A-Za-z0-9_-` and default ids use it
+    #[test]
+    fn default_alphabet() {
+        let mut sorted: Vec<char> = NANOID_ALPHABET.chars().collect();
+        sorted.sort_unstable();
+        let mut expected: Vec<char> = ('A'..='Z')
+            .chain('a'..='z')
+            .chain('0'..='9')
+            .chain(['_', '-'])
+            .collect();
+        expected.sort_unstable();
+
+        assert_eq!(expected, sorted);
+
+        let id = randid_nanoid();
+
+        assert_eq!(NANOID_DEFAULT_SIZE, id.len());
+        assert!(id.chars().all(|c| NANOID_ALPHABET.contains(c)));
+    }
+
+    /// Checks the mask and step sizes for alphabets of various sizes
+    #[test]
+    fn sizes() {
+        let mut rng = rand::thread_rng();
+
+        for size in [1, 2, 5, 64, 200, 256] {
+            let symbols: String = (0..size as u32)
+                .map(|i| std::char::from_u32(0x100 + i).unwrap())
+                .collect();
+            let alphabet = Alphabet::new(&symbols).unwrap();
+
+            assert_eq!(
+                30,
+                nanoid_with_rng(&mut rng, &alphabet, 30)
+                    .unwrap()
+                    .chars()
+                    .count()
+            );
+        }
+    }
+
+    /// Checks invalid sizes and alphabets are rejected
+    #[test]
+    fn errors() {
+        let mut rng = rand::thread_rng();
+        let symbols: String = (0..257u32)
+            .map(|i| std::char::from_u32(0x100 + i).unwrap())
+            .collect();
+
+        assert_eq!(
+            Err(RandidError::ZeroLength),
+            nanoid_with_rng(&mut rng, &Alphabet::nanoid(), 0)
+        );
+        assert_eq!(
+            Err(RandidError::InvalidAlphabet(AlphabetError::TooLarge {
+                size: 257,
+                max: 256
+            })),
+            nanoid_with_rng(&mut rng, &Alphabet::new(&symbols).unwrap(), 1)
+        );
+    }
+}
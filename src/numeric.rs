@@ -0,0 +1,174 @@
+//! Random integer IDs with a guaranteed number of decimal digits.
+
+use crate::{Alphabet, IdGenerator, RandidError};
+use rand::RngCore;
+
+/// Generates a random [u32] with exactly `digits` decimal digits.
+///
+/// Every value with the requested number of digits is equally likely. Apart from
+/// a single digit (which may be `0`), the value never has leading zeros, so
+/// formatting it always gives a string of `digits` characters.
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] if `digits` is `0`, or
+/// [RandidError::LengthExceeded] if it's more than the `10` digits a [u32] can
+/// hold.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::randid_u32;
+///
+/// fn main() {
+///     let id = randid_u32(6).unwrap(); // between 100000 and 999999
+///
+///     assert_eq!(id.to_string().len(), 6);
+/// }
+/// ```
+pub fn randid_u32(digits: usize) -> Result<u32, RandidError> {
+    digit_range(digits, u32::MAX as u128).map(|(min, max)| random_between(min, max) as u32)
+}
+
+/// Generates a random [u64] with exactly `digits` decimal digits, up to `20`.
+///
+/// See [randid_u32] for the distribution of the generated values and errors.
+pub fn randid_u64(digits: usize) -> Result<u64, RandidError> {
+    digit_range(digits, u64::MAX as u128).map(|(min, max)| random_between(min, max) as u64)
+}
+
+/// Generates a random [u128] with exactly `digits` decimal digits, up to `39`.
+///
+/// See [randid_u32] for the distribution of the generated values and errors.
+pub fn randid_u128(digits: usize) -> Result<u128, RandidError> {
+    digit_range(digits, u128::MAX).map(|(min, max)| random_between(min, max))
+}
+
+/// Generates a random zero-padded string of `len` decimal digits, like `"00396"`
+/// for a length of `5`.
+///
+/// Unlike the integer functions this isn't limited by the size of a type, as each
+/// of the ten digits is drawn independently and uniformly.
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] if `len` is `0`, or
+/// [RandidError::LengthExceeded] if it's above
+/// [DEFAULT_MAX_LENGTH](crate::DEFAULT_MAX_LENGTH).
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::randid_digits;
+///
+/// fn main() {
+///     let code = randid_digits(40).unwrap();
+///
+///     assert_eq!(code.len(), 40);
+///     assert!(code.chars().all(|c| c.is_ascii_digit()));
+/// }
+/// ```
+pub fn randid_digits(len: usize) -> Result<String, RandidError> {
+    IdGenerator::builder()
+        .alphabet(Alphabet::digits())
+        .length(len)
+        .build()
+        .map(|gen| gen.generate())
+}
+
+/// Smallest and largest values with exactly `digits` decimal digits, capped to
+/// `type_max`.
+fn digit_range(digits: usize, type_max: u128) -> Result<(u128, u128), RandidError> {
+    let max_digits = type_max.to_string().len();
+
+    if digits == 0 {
+        return Err(RandidError::ZeroLength);
+    } else if digits > max_digits {
+        return Err(RandidError::LengthExceeded {
+            length: digits,
+            max: max_digits,
+        });
+    }
+
+    let min = if digits == 1 {
+        0
+    } else {
+        10u128.pow(digits as u32 - 1)
+    };
+    let max = 10u128
+        .checked_pow(digits as u32)
+        .map_or(type_max, |limit| (limit - 1).min(type_max));
+
+    Ok((min, max))
+}
+
+/// Uniformly draws a value in `min..=max` from [rand::thread_rng].
+fn random_between(min: u128, max: u128) -> u128 {
+    min + uniform_u128(&mut rand::thread_rng(), max - min)
+}
+
+/// Uniformly draws a value in `0..=max` using the same masked rejection sampling
+/// as [Alphabet::sample].
+fn uniform_u128<R: RngCore + ?Sized>(rng: &mut R, max: u128) -> u128 {
+    let mask = u128::MAX >> max.leading_zeros();
+
+    loop {
+        let candidate = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) & mask;
+
+        if candidate <= max {
+            return candidate;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks every integer type gives the exact number of digits asked for,
+    /// right up to the limit of the type
+    #[test]
+    fn digit_counts() {
+        for digits in 1..=10 {
+            assert_eq!(digits, randid_u32(digits).unwrap().to_string().len());
+        }
+
+        for digits in 1..=20 {
+            assert_eq!(digits, randid_u64(digits).unwrap().to_string().len());
+        }
+
+        for digits in 1..=39 {
+            assert_eq!(digits, randid_u128(digits).unwrap().to_string().len());
+        }
+    }
+
+    /// Checks impossible digit counts are rejected
+    #[test]
+    fn digit_limits() {
+        assert_eq!(Err(RandidError::ZeroLength), randid_u32(0));
+        assert_eq!(
+            Err(RandidError::LengthExceeded {
+                length: 11,
+                max: 10
+            }),
+            randid_u32(11)
+        );
+        assert!(randid_u64(21).is_err());
+        assert!(randid_u128(40).is_err());
+        assert_eq!(Ok((0, 9)), digit_range(1, u32::MAX as u128));
+        assert_eq!(
+            Ok((1_000_000_000, u32::MAX as u128)),
+            digit_range(10, u32::MAX as u128)
+        );
+    }
+
+    /// Checks all ten digits are produced, including `9`
+    #[test]
+    fn all_digits() {
+        let generated = randid_digits(1000).unwrap();
+
+        for digit in '0'..='9' {
+            assert!(generated.contains(digit), "missing {}", digit);
+        }
+    }
+}
@@ -0,0 +1,436 @@
+//! Reversible obfuscation of sequential integers, see [Obfuscator].
+
+use crate::{Alphabet, AlphabetError, Blocklist, RandidError, DEFAULT_MAX_LENGTH};
+
+/// Smallest alphabet an [Obfuscator] can use, as one symbol is reserved as a
+/// separator and the rest must form a base of at least two.
+const MIN_ALPHABET_SIZE: usize = 3;
+
+/// A reversible, salted encoder of lists of [u64]s into short strings, in the
+/// style of [Hashids](https://hashids.org) and [Sqids](https://sqids.org).
+///
+/// This is useful for exposing auto-increment primary keys in URLs without
+/// revealing how many records exist or letting users guess their neighbours,
+/// and without needing a separate lookup column as with
+/// [randid_string](crate::randid_string). The salt shuffles the alphabet, so
+/// different salts give unrelated strings for the same numbers.
+///
+/// This is obfuscation rather than encryption: anybody who knows or brute-forces
+/// the alphabet and salt can decode the numbers, so don't use it for secrets.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::Obfuscator;
+///
+/// fn main() {
+///     let obfuscator = Obfuscator::builder()
+///         .salt("my application")
+///         .min_length(8)
+///         .build()
+///         .unwrap();
+///
+///     let id = obfuscator.encode(&[1, 2, 3]).unwrap();
+///
+///     assert!(id.len() >= 8);
+///     assert_eq!(obfuscator.decode(&id).unwrap(), vec![1, 2, 3]);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Obfuscator {
+    alphabet: Vec<char>,
+    min_length: usize,
+    blocklist: Blocklist,
+}
+
+impl Obfuscator {
+    /// Creates a new [ObfuscatorBuilder] with the BASE62 alphabet, no salt, no
+    /// minimum length and an empty blocklist.
+    pub fn builder() -> ObfuscatorBuilder {
+        ObfuscatorBuilder::default()
+    }
+
+    /// Encodes `numbers` into a string, which is empty if `numbers` is empty.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::BlocklistExhausted] if every possible encoding of
+    /// `numbers` contains a blocked word.
+    pub fn encode(&self, numbers: &[u64]) -> Result<String, RandidError> {
+        if numbers.is_empty() {
+            return Ok(String::new());
+        }
+
+        (0..self.alphabet.len())
+            .map(|increment| self.encode_with_increment(numbers, increment))
+            .find(|id| !self.blocklist.is_blocked(id))
+            .ok_or(RandidError::BlocklistExhausted {
+                attempts: self.alphabet.len(),
+            })
+    }
+
+    /// Decodes a string produced by [Obfuscator::encode] back into its numbers.
+    ///
+    /// # Errors
+    ///
+    /// - [RandidError::InvalidCharacter] for characters outside of the alphabet
+    /// - [RandidError::Overflow] if a number doesn't fit in a [u64]
+    /// - [RandidError::NonCanonical] if `id` decodes to numbers which encode to a
+    ///   different string, so each list of numbers has exactly one valid string
+    pub fn decode(&self, id: &str) -> Result<Vec<u64>, RandidError> {
+        let chars: Vec<char> = id.chars().collect();
+
+        if let Some((position, character)) = chars
+            .iter()
+            .enumerate()
+            .find(|(_, c)| !self.alphabet.contains(c))
+        {
+            return Err(RandidError::InvalidCharacter {
+                character: *character,
+                position,
+            });
+        }
+
+        let (prefix, mut rest) = match chars.split_first() {
+            Some(split) => split,
+            None => return Ok(Vec::new()),
+        };
+        let offset = self.alphabet.iter().position(|c| c == prefix).unwrap();
+        let mut alphabet = self.rotated(offset);
+        let mut numbers = Vec::new();
+
+        while !rest.is_empty() {
+            let separator = alphabet[0];
+            let (chunk, remaining) = match rest.iter().position(|c| *c == separator) {
+                Some(index) => (&rest[..index], Some(&rest[index + 1..])),
+                None => (rest, None),
+            };
+
+            if chunk.is_empty() {
+                break;
+            }
+
+            numbers.push(to_number(chunk, &alphabet[1..])?);
+
+            match remaining {
+                Some(remaining) => {
+                    shuffle(&mut alphabet);
+                    rest = remaining;
+                }
+                None => break,
+            }
+        }
+
+        if self.encode(&numbers)? != id {
+            return Err(RandidError::NonCanonical);
+        }
+
+        Ok(numbers)
+    }
+
+    /// Encodes `numbers` using the alphabet offset by `increment`, without
+    /// checking the blocklist.
+    fn encode_with_increment(&self, numbers: &[u64], increment: usize) -> String {
+        let len = self.alphabet.len();
+        let offset = numbers
+            .iter()
+            .enumerate()
+            .fold(numbers.len(), |acc, (i, number)| {
+                self.alphabet[(number % len as u64) as usize] as usize + i + acc
+            });
+        let mut alphabet = self.rotated((offset + increment) % len);
+        let mut id = vec![self.alphabet[(offset + increment) % len]];
+
+        for (i, number) in numbers.iter().enumerate() {
+            id.extend(to_id(*number, &alphabet[1..]));
+
+            if i < numbers.len() - 1 {
+                id.push(alphabet[0]);
+                shuffle(&mut alphabet);
+            }
+        }
+
+        if id.len() < self.min_length {
+            id.push(alphabet[0]);
+
+            while id.len() < self.min_length {
+                shuffle(&mut alphabet);
+
+                let needed = (self.min_length - id.len()).min(len);
+                id.extend_from_slice(&alphabet[..needed]);
+            }
+        }
+
+        id.into_iter().collect()
+    }
+
+    /// The alphabet rotated left by `offset` then reversed, as used for encoding
+    /// and decoding an id starting with the symbol at `offset`.
+    fn rotated(&self, offset: usize) -> Vec<char> {
+        let mut alphabet = self.alphabet.clone();
+        alphabet.rotate_left(offset);
+        alphabet.reverse();
+        alphabet
+    }
+}
+
+/// Builder for an [Obfuscator], created using [Obfuscator::builder].
+#[derive(Debug, Clone, Default)]
+pub struct ObfuscatorBuilder {
+    alphabet: Alphabet,
+    salt: String,
+    min_length: usize,
+    blocklist: Blocklist,
+}
+
+impl ObfuscatorBuilder {
+    /// Sets the alphabet of encoded strings, defaulting to [Alphabet::base62].
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Sets the salt used to shuffle the alphabet, defaulting to none.
+    pub fn salt(mut self, salt: impl Into<String>) -> Self {
+        self.salt = salt.into();
+        self
+    }
+
+    /// Sets the minimum length of encoded strings, which are padded if shorter,
+    /// defaulting to `0`.
+    pub fn min_length(mut self, min_length: usize) -> Self {
+        self.min_length = min_length;
+        self
+    }
+
+    /// Sets words which must never appear in encoded strings, defaulting to an
+    /// empty [Blocklist].
+    ///
+    /// Whenever an encoding contains a blocked word, the numbers are re-encoded
+    /// with a different offset until a clean string is found.
+    pub fn blocklist(mut self, blocklist: Blocklist) -> Self {
+        self.blocklist = blocklist;
+        self
+    }
+
+    /// Builds the final [Obfuscator].
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::InvalidAlphabet] if the alphabet has fewer than 3
+    /// symbols, or [RandidError::LengthExceeded] if the minimum length is above
+    /// [DEFAULT_MAX_LENGTH].
+    pub fn build(self) -> Result<Obfuscator, RandidError> {
+        let mut alphabet = self.alphabet.symbols().to_vec();
+
+        if alphabet.len() < MIN_ALPHABET_SIZE {
+            return Err(AlphabetError::TooSmall {
+                size: alphabet.len(),
+                min: MIN_ALPHABET_SIZE,
+            }
+            .into());
+        } else if self.min_length > DEFAULT_MAX_LENGTH {
+            return Err(RandidError::LengthExceeded {
+                length: self.min_length,
+                max: DEFAULT_MAX_LENGTH,
+            });
+        }
+
+        salt_shuffle(&mut alphabet, &self.salt.chars().collect::<Vec<_>>());
+        shuffle(&mut alphabet);
+
+        Ok(Obfuscator {
+            alphabet,
+            min_length: self.min_length,
+            blocklist: self.blocklist,
+        })
+    }
+}
+
+/// Shuffles `alphabet` keyed by `salt`, as in Hashids, doing nothing for an empty
+/// salt.
+fn salt_shuffle(alphabet: &mut [char], salt: &[char]) {
+    if salt.is_empty() {
+        return;
+    }
+
+    let mut v = 0;
+    let mut p = 0;
+
+    for i in (1..alphabet.len()).rev() {
+        v %= salt.len();
+
+        let n = salt[v] as usize;
+        p += n;
+        alphabet.swap(i, (n + v + p) % i);
+
+        v += 1;
+    }
+}
+
+/// Deterministically shuffles `alphabet` based on its own contents, as in Sqids.
+fn shuffle(alphabet: &mut [char]) {
+    let len = alphabet.len();
+    let (mut i, mut j) = (0, len - 1);
+
+    while j > 0 {
+        let r = (i * j + alphabet[i] as usize + alphabet[j] as usize) % len;
+        alphabet.swap(i, r);
+
+        i += 1;
+        j -= 1;
+    }
+}
+
+/// Encodes `number` using `alphabet` as its digits, most significant first.
+fn to_id(mut number: u64, alphabet: &[char]) -> Vec<char> {
+    let len = alphabet.len() as u64;
+    let mut id = Vec::new();
+
+    loop {
+        id.push(alphabet[(number % len) as usize]);
+        number /= len;
+
+        if number == 0 {
+            break;
+        }
+    }
+
+    id.reverse();
+    id
+}
+
+/// Decodes `id` using `alphabet` as its digits, the reverse of [to_id].
+fn to_number(id: &[char], alphabet: &[char]) -> Result<u64, RandidError> {
+    let len = alphabet.len() as u64;
+
+    id.iter().try_fold(0u64, |number, c| {
+        let digit = alphabet
+            .iter()
+            .position(|symbol| symbol == c)
+            .ok_or(RandidError::NonCanonical)?;
+
+        number
+            .checked_mul(len)
+            .and_then(|number| number.checked_add(digit as u64))
+            .ok_or(RandidError::Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks lists of numbers survive a round trip, including extremes
+    #[test]
+    fn round_trip() {
+        let obfuscator = Obfuscator::builder().build().unwrap();
+        let cases: [&[u64]; 6] = [&[0], &[1], &[u64::MAX], &[1, 2, 3], &[0, 0, 0], &[42; 10]];
+
+        for numbers in cases.iter() {
+            let id = obfuscator.encode(numbers).unwrap();
+
+            assert_eq!(numbers.to_vec(), obfuscator.decode(&id).unwrap(), "{}", id);
+        }
+
+        for number in 0..1_000 {
+            let id = obfuscator.encode(&[number]).unwrap();
+
+            assert_eq!(vec![number], obfuscator.decode(&id).unwrap());
+        }
+
+        assert_eq!("", obfuscator.encode(&[]).unwrap());
+        assert!(obfuscator.decode("").unwrap().is_empty());
+    }
+
+    /// Checks sequential numbers give distinct, unrelated strings which depend on
+    /// the salt
+    #[test]
+    fn salted() {
+        let plain = Obfuscator::builder().build().unwrap();
+        let salted = Obfuscator::builder().salt("pepper").build().unwrap();
+
+        assert_ne!(plain.encode(&[1]).unwrap(), salted.encode(&[1]).unwrap());
+        assert_ne!(salted.encode(&[1]).unwrap(), salted.encode(&[2]).unwrap());
+        assert_eq!(
+            vec![7],
+            salted.decode(&salted.encode(&[7]).unwrap()).unwrap()
+        );
+    }
+
+    /// Checks short strings are padded up to the minimum length
+    #[test]
+    fn min_length() {
+        let obfuscator = Obfuscator::builder()
+            .alphabet(Alphabet::new("abc").unwrap())
+            .min_length(20)
+            .build()
+            .unwrap();
+
+        for number in 0..100 {
+            let id = obfuscator.encode(&[number, number + 1]).unwrap();
+
+            assert_eq!(20, id.len());
+            assert_eq!(vec![number, number + 1], obfuscator.decode(&id).unwrap());
+        }
+    }
+
+    /// Checks blocked words are avoided, ignoring case
+    #[test]
+    fn blocklist() {
+        let plain = Obfuscator::builder().build().unwrap();
+        let blocked = plain.encode(&[100]).unwrap().to_uppercase();
+        let obfuscator = Obfuscator::builder()
+            .blocklist(Blocklist::new(vec![blocked.clone()]))
+            .build()
+            .unwrap();
+        let id = obfuscator.encode(&[100]).unwrap();
+
+        assert_ne!(blocked, id.to_uppercase());
+        assert_eq!(vec![100], obfuscator.decode(&id).unwrap());
+    }
+
+    /// Checks every encoding being blocked gives an error
+    #[test]
+    fn blocklist_exhausted() {
+        let obfuscator = Obfuscator::builder()
+            .alphabet(Alphabet::new("abc").unwrap())
+            .blocklist(Blocklist::new(vec!["a", "b", "c"]))
+            .build()
+            .unwrap();
+
+        assert_eq!(
+            Err(RandidError::BlocklistExhausted { attempts: 3 }),
+            obfuscator.encode(&[1])
+        );
+    }
+
+    /// Checks malformed and non-canonical strings are rejected
+    #[test]
+    fn decode_errors() {
+        let obfuscator = Obfuscator::builder().build().unwrap();
+        let id = obfuscator.encode(&[5]).unwrap();
+
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '-',
+                position: 0
+            }),
+            obfuscator.decode("-")
+        );
+        assert_eq!(
+            Err(RandidError::NonCanonical),
+            obfuscator.decode(&format!("{}{}", id, id))
+        );
+        assert_eq!(
+            Err(RandidError::InvalidAlphabet(AlphabetError::TooSmall {
+                size: 2,
+                min: 3
+            })),
+            Obfuscator::builder()
+                .alphabet(Alphabet::new("ab").unwrap())
+                .build()
+                .map(|_| ())
+        );
+    }
+}
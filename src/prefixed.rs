@@ -0,0 +1,324 @@
+//! Typed IDs tagged with the kind of entity they identify, see [PrefixedId].
+
+use crate::{Alphabet, RandidError};
+use rand::RngCore;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// A kind of entity identified by a [PrefixedId], usually declared with
+/// [prefixed_id!](crate::prefixed_id).
+pub trait IdKind {
+    /// Text placed before the `_` separator of each ID, like `user`.
+    const PREFIX: &'static str;
+
+    /// Number of random BASE62 symbols after the separator, which must be above
+    /// `0`.
+    const LENGTH: usize = 16;
+}
+
+/// A Stripe-style ID made of a prefix naming the kind of entity, an `_` and a
+/// random BASE62 body, like `user_2x4Bf9qTz7LmR0aK`.
+///
+/// The kind `T` is part of the type, so a `PrefixedId<User>` can't be passed
+/// where a `PrefixedId<Order>` is expected, and parsing text with the wrong
+/// prefix fails. `T` is only a marker, so it doesn't need to implement any
+/// traits itself and is usually an empty enum declared by
+/// [prefixed_id!](crate::prefixed_id).
+///
+/// Bodies are drawn from [rand::thread_rng] by [PrefixedId::new]. When IDs act
+/// as secrets, use [PrefixedId::with_rng] with [OsRng](rand::rngs::OsRng), see
+/// the [crate-level security notes](crate#security).
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{prefixed_id, PrefixedId};
+///
+/// prefixed_id!(pub User = "user");
+/// prefixed_id!(pub Order = "ord", 24);
+///
+/// type UserId = PrefixedId<User>;
+/// type OrderId = PrefixedId<Order>;
+///
+/// fn main() {
+///     let user = UserId::new();
+///     let order = OrderId::new();
+///
+///     assert!(user.to_string().starts_with("user_"));
+///     assert_eq!(order.body().len(), 24);
+///
+///     assert_eq!(user, user.to_string().parse().unwrap());
+///     assert!(order.to_string().parse::<UserId>().is_err());
+/// }
+/// ```
+pub struct PrefixedId<T> {
+    id: String,
+    kind: PhantomData<fn() -> T>,
+}
+
+impl<T: IdKind> PrefixedId<T> {
+    /// Generates a new ID using [rand::thread_rng].
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a new ID using the provided random number generator.
+    pub fn with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let alphabet = Alphabet::base62();
+        let mut id = String::with_capacity(T::PREFIX.len() + 1 + T::LENGTH);
+
+        id.push_str(T::PREFIX);
+        id.push('_');
+        id.extend((0..T::LENGTH).map(|_| alphabet.sample(rng)));
+
+        Self {
+            id,
+            kind: PhantomData,
+        }
+    }
+
+    /// The random body of this ID, after the prefix and separator.
+    pub fn body(&self) -> &str {
+        &self.id[T::PREFIX.len() + 1..]
+    }
+}
+
+impl<T> PrefixedId<T> {
+    /// The full text of this ID, including its prefix.
+    pub fn as_str(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<T: IdKind> Default for PrefixedId<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for PrefixedId<T> {
+    fn clone(&self) -> Self {
+        Self {
+            id: self.id.clone(),
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<T> PartialEq for PrefixedId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T> Eq for PrefixedId<T> {}
+
+impl<T> Hash for PrefixedId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state)
+    }
+}
+
+impl<T> PartialOrd for PrefixedId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for PrefixedId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl<T> fmt::Debug for PrefixedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("PrefixedId").field(&self.id).finish()
+    }
+}
+
+impl<T> fmt::Display for PrefixedId<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.id)
+    }
+}
+
+impl<T: IdKind> FromStr for PrefixedId<T> {
+    type Err = RandidError;
+
+    /// Parses an ID, checking its prefix, body length and that the body is
+    /// BASE62.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let body = s
+            .strip_prefix(T::PREFIX)
+            .and_then(|rest| rest.strip_prefix('_'))
+            .ok_or_else(|| RandidError::InvalidPrefix {
+                expected: format!("{}_", T::PREFIX),
+            })?;
+        let found = body.chars().count();
+
+        if found != T::LENGTH {
+            return Err(RandidError::InvalidLength {
+                expected: T::LENGTH,
+                found,
+            });
+        }
+
+        let alphabet = Alphabet::base62();
+        let offset = s.chars().count() - found;
+
+        if let Some((position, character)) = body
+            .chars()
+            .enumerate()
+            .find(|(_, c)| !alphabet.contains(*c))
+        {
+            return Err(RandidError::InvalidCharacter {
+                character,
+                position: offset + position,
+            });
+        }
+
+        Ok(Self {
+            id: s.to_string(),
+            kind: PhantomData,
+        })
+    }
+}
+
+impl<T> AsRef<str> for PrefixedId<T> {
+    fn as_ref(&self) -> &str {
+        &self.id
+    }
+}
+
+impl<T> From<PrefixedId<T>> for String {
+    fn from(id: PrefixedId<T>) -> Self {
+        id.id
+    }
+}
+
+/// Declares an empty marker type implementing [IdKind], for use with
+/// [PrefixedId].
+///
+/// Takes the visibility and name of the marker, its prefix and optionally the
+/// length of its random body, which defaults to `16`. Attributes such as doc
+/// comments are passed through to the marker.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{prefixed_id, IdKind, PrefixedId};
+///
+/// prefixed_id!(
+///     /// Customers of the shop.
+///     pub Customer = "cus"
+/// );
+/// prefixed_id!(Invoice = "in", 24);
+///
+/// fn main() {
+///     assert_eq!(Customer::PREFIX, "cus");
+///     assert_eq!(PrefixedId::<Invoice>::new().body().len(), 24);
+/// }
+/// ```
+#[macro_export]
+macro_rules! prefixed_id {
+    ($(#[$meta:meta])* $vis:vis $name:ident = $prefix:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis enum $name {}
+
+        impl $crate::IdKind for $name {
+            const PREFIX: &'static str = $prefix;
+        }
+    };
+    ($(#[$meta:meta])* $vis:vis $name:ident = $prefix:expr, $length:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        $vis enum $name {}
+
+        impl $crate::IdKind for $name {
+            const PREFIX: &'static str = $prefix;
+            const LENGTH: usize = $length;
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    prefixed_id!(User = "user");
+    prefixed_id!(Order = "ord", 6);
+
+    /// Marker type without any derives, to check [PrefixedId] needs none
+    struct Bare;
+
+    impl IdKind for Bare {
+        const PREFIX: &'static str = "bare";
+    }
+
+    /// Checks generated ids have their prefix and length and survive a round trip
+    #[test]
+    fn round_trip() {
+        let user = PrefixedId::<User>::new();
+        let order = PrefixedId::<Order>::new();
+
+        assert_eq!(21, user.as_str().len());
+        assert_eq!("ord_", &order.as_str()[..4]);
+        assert_eq!(6, order.body().len());
+        assert_eq!(user, user.to_string().parse().unwrap());
+        assert_eq!(order.as_str(), String::from(order.clone()));
+    }
+
+    /// Checks the comparison traits work without bounds on the marker type
+    #[test]
+    fn traits() {
+        let first = PrefixedId::<Bare>::new();
+        let second = first.clone();
+        let mut set = HashSet::new();
+
+        set.insert(first.clone());
+        set.insert(second.clone());
+
+        assert_eq!(1, set.len());
+        assert_eq!(first.cmp(&second), Ordering::Equal);
+        assert!("bare_0000000000000000".parse::<PrefixedId<Bare>>().unwrap() < first);
+    }
+
+    /// Checks wrong prefixes, lengths and characters are rejected
+    #[test]
+    fn parse_errors() {
+        let order = PrefixedId::<Order>::new().to_string();
+
+        assert_eq!(
+            Err(RandidError::InvalidPrefix {
+                expected: "user_".to_string()
+            }),
+            order.parse::<PrefixedId<User>>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidPrefix {
+                expected: "ord_".to_string()
+            }),
+            "ordAbc123".parse::<PrefixedId<Order>>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidLength {
+                expected: 6,
+                found: 5
+            }),
+            "ord_abc12".parse::<PrefixedId<Order>>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '-',
+                position: 6
+            }),
+            "ord_ab-123".parse::<PrefixedId<Order>>()
+        );
+    }
+}
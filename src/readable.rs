@@ -0,0 +1,92 @@
+//! Parsing of human-entered readable IDs, see [normalize_readable].
+
+use crate::ulid::CROCKFORD;
+use crate::RandidError;
+
+/// Normalises an ID made from [Alphabet::readable](crate::Alphabet::readable)
+/// which was typed in by a person into its canonical form.
+///
+/// Letters are uppercased, the common misreadings `O` for `0` and `I` or `L` for
+/// `1` are corrected, and group separators (`-`, `_` and whitespace) are
+/// removed. The result is the bare, uppercase random symbols, so compare it
+/// against IDs stored without separators, or normalise those too.
+///
+/// # Errors
+///
+/// Returns [RandidError::ZeroLength] if nothing is left after removing
+/// separators, or [RandidError::InvalidCharacter] for any other character
+/// outside of the readable alphabet, such as `U`.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::normalize_readable;
+///
+/// fn main() {
+///     assert_eq!(normalize_readable("7kqm-3xza").unwrap(), "7KQM3XZA");
+///     assert_eq!(normalize_readable(" IO1L 2345 ").unwrap(), "10112345");
+///     assert!(normalize_readable("ABCU").is_err());
+/// }
+/// ```
+pub fn normalize_readable(text: &str) -> Result<String, RandidError> {
+    let mut normalized = String::with_capacity(text.len());
+
+    for (position, character) in text.chars().enumerate() {
+        let symbol = match character.to_ascii_uppercase() {
+            '-' | '_' => continue,
+            c if c.is_whitespace() => continue,
+            'O' => '0',
+            'I' | 'L' => '1',
+            c if CROCKFORD.contains(c) => c,
+            _ => {
+                return Err(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                })
+            }
+        };
+
+        normalized.push(symbol);
+    }
+
+    if normalized.is_empty() {
+        return Err(RandidError::ZeroLength);
+    }
+
+    Ok(normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alphabet;
+
+    /// Checks the readable alphabet excludes ambiguous characters
+    #[test]
+    fn alphabet() {
+        let readable = Alphabet::readable();
+
+        assert_eq!(32, readable.size());
+        assert!("ILOUilou".chars().all(|c| !readable.contains(c)));
+        assert_eq!(
+            readable.to_string(),
+            normalize_readable(&readable.to_string().to_lowercase()).unwrap()
+        );
+    }
+
+    /// Checks misreadings are corrected and unknown characters are rejected with
+    /// their position in the original text
+    #[test]
+    fn normalize() {
+        assert_eq!("0011", normalize_readable("oO-iL").unwrap());
+        assert_eq!("ABCD", normalize_readable("ab\tc_d").unwrap());
+        assert_eq!(Err(RandidError::ZeroLength), normalize_readable(" - "));
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: 'u',
+                position: 5
+            }),
+            normalize_readable("ABCD-u")
+        );
+    }
+}
@@ -0,0 +1,235 @@
+//! [Serialize] and [Deserialize] implementations for the ID types, enabled by the
+//! `serde` feature.
+//!
+//! Every ID is serialized as its string form and validated in the same way as
+//! [FromStr] when deserialized, so malformed IDs in a payload are rejected with
+//! the message of the [RandidError](crate::RandidError) describing the problem,
+//! such as `invalid character '-' at position 5`.
+
+use crate::{IdKind, Ksuid, PrefixedId, RandId, Snowflake, Ulid, Uuid};
+use serde::de::{self, Deserialize, Deserializer, Visitor};
+use serde::ser::{Serialize, Serializer};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+/// Visitor deserializing any ID type from a string using its [FromStr]
+/// implementation.
+struct ParseVisitor<T> {
+    name: &'static str,
+    kind: PhantomData<fn() -> T>,
+}
+
+impl<T> ParseVisitor<T> {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> Visitor<'de> for ParseVisitor<T>
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    type Value = T;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a string holding a valid {}", self.name)
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse()
+            .map_err(|err| E::custom(format_args!("invalid {}, {}", self.name, err)))
+    }
+}
+
+/// Implements [Serialize] and [Deserialize] through the [Display](fmt::Display)
+/// and [FromStr] implementations of each type.
+macro_rules! impl_serde_str {
+    ($($ty:ty => $name:expr),* $(,)?) => {
+        $(
+            impl Serialize for $ty {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $ty {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    deserializer.deserialize_str(ParseVisitor::new($name))
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_str! {
+    Uuid => "UUID",
+    Ulid => "ULID",
+    Ksuid => "KSUID",
+}
+
+impl<T> Serialize for PrefixedId<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, T: IdKind> Deserialize<'de> for PrefixedId<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ParseVisitor::new("prefixed ID"))
+    }
+}
+
+impl<const N: usize> Serialize for RandId<N> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de, const N: usize> Deserialize<'de> for RandId<N> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_str(ParseVisitor::new("ID"))
+    }
+}
+
+/// Snowflakes are serialized as decimal strings in human-readable formats, as
+/// most JSON parsers can't hold all 63 bits in a number, but may be deserialized
+/// from either a string or an integer. Compact binary formats use a plain [u64].
+impl Serialize for Snowflake {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.as_u64())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Snowflake {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Visitor accepting both forms of a [Snowflake].
+        struct SnowflakeVisitor;
+
+        impl<'de> Visitor<'de> for SnowflakeVisitor {
+            type Value = Snowflake;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a snowflake as a decimal string or integer")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                ParseVisitor::new("snowflake").visit_str(v)
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                Snowflake::from_u64(v)
+                    .map_err(|err| E::custom(format_args!("invalid snowflake, {}", err)))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                if v < 0 {
+                    return Err(E::invalid_value(de::Unexpected::Signed(v), &self));
+                }
+
+                self.visit_u64(v as u64)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(SnowflakeVisitor)
+        } else {
+            deserializer.deserialize_u64(SnowflakeVisitor)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{prefixed_id, Ksuid, PrefixedId, RandId, Snowflake, Ulid, Uuid};
+
+    prefixed_id!(User = "user", 8);
+
+    /// Checks every id type round trips through its JSON string form
+    #[test]
+    fn round_trip() {
+        let uuid = Uuid::new_v4();
+        let ulid = Ulid::new();
+        let ksuid = Ksuid::new();
+        let user = PrefixedId::<User>::new();
+
+        assert_eq!(
+            format!("\"{}\"", uuid),
+            serde_json::to_string(&uuid).unwrap()
+        );
+        assert_eq!(
+            uuid,
+            serde_json::from_str(&serde_json::to_string(&uuid).unwrap()).unwrap()
+        );
+        assert_eq!(
+            ulid,
+            serde_json::from_str(&serde_json::to_string(&ulid).unwrap()).unwrap()
+        );
+        assert_eq!(
+            ksuid,
+            serde_json::from_str(&serde_json::to_string(&ksuid).unwrap()).unwrap()
+        );
+        assert_eq!(
+            user,
+            serde_json::from_str(&serde_json::to_string(&user).unwrap()).unwrap()
+        );
+    }
+
+    /// Checks snowflakes serialize as strings but accept integers too
+    #[test]
+    fn snowflake() {
+        let snowflake: Snowflake = serde_json::from_str("9223372036854775807").unwrap();
+
+        assert_eq!(
+            "\"9223372036854775807\"",
+            serde_json::to_string(&snowflake).unwrap()
+        );
+        assert_eq!(
+            snowflake,
+            serde_json::from_str("\"9223372036854775807\"").unwrap()
+        );
+        assert!(serde_json::from_str::<Snowflake>("-1").is_err());
+        assert!(serde_json::from_str::<Snowflake>("9223372036854775808").is_err());
+    }
+
+    /// Checks snowflakes round trip through compact formats as plain integers
+    #[test]
+    fn snowflake_compact() {
+        let snowflake = Snowflake::from_u64(1_234_567_890_123).unwrap();
+        let bytes = bincode::serialize(&snowflake).unwrap();
+
+        assert_eq!(1_234_567_890_123u64.to_le_bytes().to_vec(), bytes);
+        assert_eq!(snowflake, bincode::deserialize(&bytes).unwrap());
+        assert!(bincode::deserialize::<Snowflake>(&u64::MAX.to_le_bytes()).is_err());
+    }
+
+    /// Checks invalid ids are rejected with a precise message
+    #[test]
+    fn errors() {
+        let err = serde_json::from_str::<PrefixedId<User>>("\"user_abc-1234\"").unwrap_err();
+
+        assert_eq!(
+            "invalid prefixed ID, invalid character '-' at position 8 at line 1 column 15",
+            err.to_string()
+        );
+        assert!(serde_json::from_str::<PrefixedId<User>>("\"ord_abcd1234\"")
+            .unwrap_err()
+            .to_string()
+            .contains("expected a prefix of \"user_\""));
+        assert!(serde_json::from_str::<Ulid>("42").is_err());
+        assert_eq!(
+            "invalid ID, expected a length of 4 but found 3 at line 1 column 5",
+            serde_json::from_str::<RandId<4>>("\"abc\"")
+                .unwrap_err()
+                .to_string()
+        );
+    }
+}
@@ -0,0 +1,471 @@
+//! Time-ordered 64-bit integer IDs for distributed workers, see
+//! [SnowflakeGenerator].
+
+use crate::base62;
+use crate::clock;
+use crate::RandidError;
+use std::fmt;
+use std::str::FromStr;
+
+/// Default custom epoch of snowflakes, `2020-01-01T00:00:00Z` in milliseconds
+/// since the Unix epoch.
+pub const DEFAULT_SNOWFLAKE_EPOCH: u64 = 1_577_836_800_000;
+
+/// Number of bits available to the timestamp, datacenter, worker and sequence
+/// fields, leaving the sign bit unset so snowflakes also fit in an [i64].
+const TOTAL_BITS: u32 = 63;
+
+/// Largest value which fits in `bits` bits.
+const fn max_for_bits(bits: u32) -> u64 {
+    (1 << bits) - 1
+}
+
+/// A 64-bit, time-ordered ID produced by a [SnowflakeGenerator].
+///
+/// Renders as a decimal number using [Display](fmt::Display), or as an 11
+/// character BASE62 string using [Snowflake::to_base62] which sorts in the same
+/// order as the numbers themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Snowflake(u64);
+
+impl Snowflake {
+    /// Creates a snowflake from its value, which must fit in 63 bits.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::ValueTooLarge] if the sign bit of `value` is set.
+    pub fn from_u64(value: u64) -> Result<Self, RandidError> {
+        let max = max_for_bits(TOTAL_BITS);
+
+        if value > max {
+            return Err(RandidError::ValueTooLarge { value, max });
+        }
+
+        Ok(Self(value))
+    }
+
+    /// Value of this snowflake as a [u64].
+    pub const fn as_u64(&self) -> u64 {
+        self.0
+    }
+
+    /// Fixed-width BASE62 form, always 11 characters long.
+    pub fn to_base62(&self) -> String {
+        base62::encode_u128_padded(self.0 as u128, 11)
+    }
+}
+
+impl fmt::Display for Snowflake {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Snowflake {
+    type Err = RandidError;
+
+    /// Parses the decimal form of a snowflake, which must fit in 63 bits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(RandidError::ZeroLength);
+        }
+
+        let mut value = 0u64;
+
+        for (position, character) in s.chars().enumerate() {
+            let digit = character
+                .to_digit(10)
+                .ok_or(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                })?;
+
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(digit as u64))
+                .ok_or(RandidError::Overflow)?;
+        }
+
+        Self::from_u64(value)
+    }
+}
+
+impl From<Snowflake> for u64 {
+    fn from(snowflake: Snowflake) -> Self {
+        snowflake.0
+    }
+}
+
+/// The individual fields of a [Snowflake], see [SnowflakeGenerator::parts].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SnowflakeParts {
+    /// Milliseconds since the Unix epoch the snowflake was generated at.
+    pub timestamp_ms: u64,
+    /// Datacenter the snowflake was generated in.
+    pub datacenter_id: u64,
+    /// Worker the snowflake was generated by.
+    pub worker_id: u64,
+    /// Position of the snowflake within its millisecond.
+    pub sequence: u64,
+}
+
+/// A generator of collision-free [Snowflake]s, Twitter-style.
+///
+/// Each snowflake is laid out from the most significant bit as the milliseconds
+/// since a custom epoch, a datacenter ID, a worker ID and a sequence number which
+/// counts up for IDs generated within the same millisecond. As long as every
+/// worker has a unique datacenter and worker ID pair, snowflakes never collide.
+///
+/// The widths of each field default to 5 datacenter bits, 5 worker bits and 12
+/// sequence bits, leaving 41 bits for the timestamp (roughly 69 years), and can be
+/// changed using [SnowflakeGenerator::builder].
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::SnowflakeGenerator;
+///
+/// fn main() {
+///     let mut gen = SnowflakeGenerator::builder()
+///         .datacenter_id(1)
+///         .worker_id(7)
+///         .build()
+///         .unwrap();
+///
+///     let first = gen.generate().unwrap();
+///     let second = gen.generate().unwrap();
+///
+///     assert!(first < second);
+///     assert_eq!(gen.parts(second).worker_id, 7);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SnowflakeGenerator {
+    epoch_ms: u64,
+    datacenter_id: u64,
+    worker_id: u64,
+    worker_bits: u32,
+    sequence_bits: u32,
+    timestamp_bits: u32,
+    last_ms: Option<u64>,
+    sequence: u64,
+}
+
+impl SnowflakeGenerator {
+    /// Creates a new [SnowflakeBuilder] with the default layout, an epoch of
+    /// [DEFAULT_SNOWFLAKE_EPOCH] and datacenter and worker IDs of `0`.
+    pub fn builder() -> SnowflakeBuilder {
+        SnowflakeBuilder::default()
+    }
+
+    /// Generates the next snowflake for the current time.
+    ///
+    /// # Errors
+    ///
+    /// - [RandidError::ClockMovedBackwards] if the clock is behind the time of the
+    ///   last snowflake, as continuing could produce duplicates
+    /// - [RandidError::SequenceExhausted] if every sequence number for the current
+    ///   millisecond has been used, in which case retrying in the next millisecond
+    ///   will succeed
+    /// - [RandidError::Overflow] if the clock is before the epoch or too far past
+    ///   it to fit in the timestamp bits
+    pub fn generate(&mut self) -> Result<Snowflake, RandidError> {
+        self.generate_at(clock::now_millis())
+    }
+
+    /// Generates the next snowflake for the given number of milliseconds since the
+    /// Unix epoch.
+    ///
+    /// Errors are the same as [SnowflakeGenerator::generate].
+    pub fn generate_at(&mut self, unix_ms: u64) -> Result<Snowflake, RandidError> {
+        let elapsed = unix_ms
+            .checked_sub(self.epoch_ms)
+            .filter(|elapsed| *elapsed <= max_for_bits(self.timestamp_bits))
+            .ok_or(RandidError::Overflow)?;
+
+        match self.last_ms {
+            Some(last_ms) if unix_ms < last_ms => {
+                return Err(RandidError::ClockMovedBackwards {
+                    last_ms,
+                    now_ms: unix_ms,
+                })
+            }
+            Some(last_ms) if unix_ms == last_ms => {
+                if self.sequence == max_for_bits(self.sequence_bits) {
+                    return Err(RandidError::SequenceExhausted {
+                        timestamp_ms: unix_ms,
+                    });
+                }
+
+                self.sequence += 1;
+            }
+            _ => self.sequence = 0,
+        }
+
+        self.last_ms = Some(unix_ms);
+
+        Ok(Snowflake(
+            elapsed << self.timestamp_shift()
+                | self.datacenter_id << self.datacenter_shift()
+                | self.worker_id << self.sequence_bits
+                | self.sequence,
+        ))
+    }
+
+    /// Splits a snowflake generated with the same layout and epoch as this
+    /// generator back into its fields.
+    pub fn parts(&self, snowflake: Snowflake) -> SnowflakeParts {
+        let value = snowflake.0;
+        let datacenter_bits = self.timestamp_shift() - self.datacenter_shift();
+
+        SnowflakeParts {
+            timestamp_ms: (value >> self.timestamp_shift()) + self.epoch_ms,
+            datacenter_id: (value >> self.datacenter_shift()) & max_for_bits(datacenter_bits),
+            worker_id: (value >> self.sequence_bits) & max_for_bits(self.worker_bits),
+            sequence: value & max_for_bits(self.sequence_bits),
+        }
+    }
+
+    /// Bit position of the datacenter field.
+    fn datacenter_shift(&self) -> u32 {
+        self.sequence_bits + self.worker_bits
+    }
+
+    /// Bit position of the timestamp field.
+    fn timestamp_shift(&self) -> u32 {
+        TOTAL_BITS - self.timestamp_bits
+    }
+}
+
+/// Builder for a [SnowflakeGenerator], created using
+/// [SnowflakeGenerator::builder].
+#[derive(Debug, Clone)]
+pub struct SnowflakeBuilder {
+    epoch_ms: u64,
+    datacenter_id: u64,
+    worker_id: u64,
+    datacenter_bits: u32,
+    worker_bits: u32,
+    sequence_bits: u32,
+}
+
+impl Default for SnowflakeBuilder {
+    fn default() -> Self {
+        Self {
+            epoch_ms: DEFAULT_SNOWFLAKE_EPOCH,
+            datacenter_id: 0,
+            worker_id: 0,
+            datacenter_bits: 5,
+            worker_bits: 5,
+            sequence_bits: 12,
+        }
+    }
+}
+
+impl SnowflakeBuilder {
+    /// Sets the custom epoch timestamps are counted from, in milliseconds since
+    /// the Unix epoch, defaulting to [DEFAULT_SNOWFLAKE_EPOCH].
+    pub fn epoch_ms(mut self, epoch_ms: u64) -> Self {
+        self.epoch_ms = epoch_ms;
+        self
+    }
+
+    /// Sets the ID of the datacenter this generator runs in, defaulting to `0`.
+    pub fn datacenter_id(mut self, datacenter_id: u64) -> Self {
+        self.datacenter_id = datacenter_id;
+        self
+    }
+
+    /// Sets the ID of this worker within its datacenter, defaulting to `0`.
+    pub fn worker_id(mut self, worker_id: u64) -> Self {
+        self.worker_id = worker_id;
+        self
+    }
+
+    /// Sets the number of bits used for the datacenter ID, defaulting to `5`.
+    pub fn datacenter_bits(mut self, bits: u32) -> Self {
+        self.datacenter_bits = bits;
+        self
+    }
+
+    /// Sets the number of bits used for the worker ID, defaulting to `5`.
+    pub fn worker_bits(mut self, bits: u32) -> Self {
+        self.worker_bits = bits;
+        self
+    }
+
+    /// Sets the number of bits used for the per-millisecond sequence, defaulting
+    /// to `12`.
+    pub fn sequence_bits(mut self, bits: u32) -> Self {
+        self.sequence_bits = bits;
+        self
+    }
+
+    /// Builds the final [SnowflakeGenerator], validating its layout.
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::ValueTooLarge] if the datacenter, worker and sequence
+    /// bits leave no room for the timestamp, or if the datacenter or worker IDs
+    /// don't fit in their bits.
+    pub fn build(self) -> Result<SnowflakeGenerator, RandidError> {
+        let field_bits =
+            self.datacenter_bits as u64 + self.worker_bits as u64 + self.sequence_bits as u64;
+
+        if field_bits >= TOTAL_BITS as u64 {
+            return Err(RandidError::ValueTooLarge {
+                value: field_bits,
+                max: TOTAL_BITS as u64 - 1,
+            });
+        }
+
+        for (id, bits) in [
+            (self.datacenter_id, self.datacenter_bits),
+            (self.worker_id, self.worker_bits),
+        ] {
+            if id > max_for_bits(bits) {
+                return Err(RandidError::ValueTooLarge {
+                    value: id,
+                    max: max_for_bits(bits),
+                });
+            }
+        }
+
+        Ok(SnowflakeGenerator {
+            epoch_ms: self.epoch_ms,
+            datacenter_id: self.datacenter_id,
+            worker_id: self.worker_id,
+            worker_bits: self.worker_bits,
+            sequence_bits: self.sequence_bits,
+            timestamp_bits: TOTAL_BITS - field_bits as u32,
+            last_ms: None,
+            sequence: 0,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the layout of a snowflake and splitting it back into parts
+    #[test]
+    fn layout() {
+        let mut gen = SnowflakeGenerator::builder()
+            .epoch_ms(1_000)
+            .datacenter_id(3)
+            .worker_id(9)
+            .build()
+            .unwrap();
+        let snowflake = gen.generate_at(1_005).unwrap();
+
+        assert_eq!(5 << 22 | 3 << 17 | 9 << 12, snowflake.as_u64());
+        assert_eq!(
+            SnowflakeParts {
+                timestamp_ms: 1_005,
+                datacenter_id: 3,
+                worker_id: 9,
+                sequence: 0
+            },
+            gen.parts(snowflake)
+        );
+    }
+
+    /// Checks the sequence counts up within a millisecond and resets after
+    #[test]
+    fn sequence() {
+        let mut gen = SnowflakeGenerator::builder().epoch_ms(0).build().unwrap();
+
+        let first = gen.generate_at(10).unwrap();
+        let second = gen.generate_at(10).unwrap();
+        let third = gen.generate_at(11).unwrap();
+
+        assert_eq!(first.as_u64() + 1, second.as_u64());
+        assert_eq!(0, gen.parts(third).sequence);
+        assert!(second < third);
+    }
+
+    /// Checks the defined errors for clock regression, sequence exhaustion and
+    /// times outside of the epoch
+    #[test]
+    fn errors() {
+        let mut gen = SnowflakeGenerator::builder()
+            .epoch_ms(100)
+            .sequence_bits(1)
+            .build()
+            .unwrap();
+
+        gen.generate_at(200).unwrap();
+        gen.generate_at(200).unwrap();
+
+        assert_eq!(
+            Err(RandidError::SequenceExhausted { timestamp_ms: 200 }),
+            gen.generate_at(200)
+        );
+        assert_eq!(
+            Err(RandidError::ClockMovedBackwards {
+                last_ms: 200,
+                now_ms: 199
+            }),
+            gen.generate_at(199)
+        );
+        assert_eq!(Err(RandidError::Overflow), gen.generate_at(99));
+        assert!(gen.generate_at(201).is_ok());
+    }
+
+    /// Checks invalid layouts and ids are rejected
+    #[test]
+    fn build_errors() {
+        assert_eq!(
+            RandidError::ValueTooLarge { value: 32, max: 31 },
+            SnowflakeGenerator::builder()
+                .worker_id(32)
+                .build()
+                .unwrap_err()
+        );
+        assert_eq!(
+            RandidError::ValueTooLarge { value: 63, max: 62 },
+            SnowflakeGenerator::builder()
+                .sequence_bits(53)
+                .build()
+                .unwrap_err()
+        );
+    }
+
+    /// Checks the BASE62 form is fixed-width and keeps ordering
+    #[test]
+    fn base62_order() {
+        let small = Snowflake(61);
+        let large = Snowflake(i64::MAX as u64);
+
+        assert_eq!("0000000000z", small.to_base62());
+        assert_eq!("AzL8n0Y58m7", large.to_base62());
+        assert!(small.to_base62() < large.to_base62());
+    }
+
+    /// Checks decimal parsing round trips and rejects values above 63 bits
+    #[test]
+    fn parse() {
+        let max = Snowflake(i64::MAX as u64);
+
+        assert_eq!(max, max.to_string().parse().unwrap());
+        assert_eq!(
+            Err(RandidError::ValueTooLarge {
+                value: 1 << 63,
+                max: i64::MAX as u64
+            }),
+            "9223372036854775808".parse::<Snowflake>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '-',
+                position: 0
+            }),
+            "-1".parse::<Snowflake>()
+        );
+        assert_eq!(
+            Err(RandidError::Overflow),
+            "99999999999999999999".parse::<Snowflake>()
+        );
+    }
+}
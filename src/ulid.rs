@@ -0,0 +1,307 @@
+//! Lexicographically sortable [ULIDs](https://github.com/ulid/spec), see [Ulid].
+
+use crate::clock;
+use crate::RandidError;
+use rand::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Crockford's base32 alphabet, which excludes `I`, `L`, `O` and `U`.
+pub(crate) const CROCKFORD: &str = "0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+/// Number of bits of randomness in each [Ulid].
+const RANDOM_BITS: u32 = 80;
+
+/// Largest possible random part of a [Ulid].
+const RANDOM_MAX: u128 = (1 << RANDOM_BITS) - 1;
+
+/// Largest possible timestamp of a [Ulid].
+const TIMESTAMP_MAX: u64 = (1 << 48) - 1;
+
+/// A 128-bit universally unique lexicographically sortable identifier.
+///
+/// Each ULID is made of a 48-bit millisecond Unix timestamp followed by 80 bits of
+/// randomness and is rendered as 26 characters of Crockford's base32, like
+/// `01ARZ3NDEKTSV4RRFFQ69G5FAV`. As the timestamp comes first, both the ULIDs
+/// themselves and their string forms sort by the time they were generated.
+///
+/// For strict ordering of ULIDs generated within the same millisecond, use a
+/// [UlidGenerator] instead.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::Ulid;
+///
+/// fn main() {
+///     let id = Ulid::new();
+///     let parsed: Ulid = id.to_string().parse().unwrap();
+///
+///     assert_eq!(id, parsed);
+///     assert_eq!(id.timestamp_ms(), parsed.timestamp_ms());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Ulid(u128);
+
+impl Ulid {
+    /// Length of the string form of a ULID.
+    pub const LEN: usize = 26;
+
+    /// Generates a ULID for the current time using [rand::thread_rng].
+    pub fn new() -> Self {
+        Self::with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a ULID for the current time using the provided random number
+    /// generator.
+    pub fn with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self::at(clock::now_millis(), rng)
+    }
+
+    /// Generates a ULID for the given number of milliseconds since the Unix epoch,
+    /// only the lower 48 bits of which are used.
+    pub fn at<R: RngCore + ?Sized>(unix_ms: u64, rng: &mut R) -> Self {
+        let random = ((rng.next_u64() as u128) << 64 | rng.next_u64() as u128) & RANDOM_MAX;
+
+        Self::from_parts(unix_ms, random)
+    }
+
+    /// Creates a ULID from a millisecond timestamp and random part, using only the
+    /// lower 48 and 80 bits of each respectively.
+    pub const fn from_parts(unix_ms: u64, random: u128) -> Self {
+        Self(((unix_ms & TIMESTAMP_MAX) as u128) << RANDOM_BITS | (random & RANDOM_MAX))
+    }
+
+    /// Milliseconds since the Unix epoch this ULID was generated at.
+    pub const fn timestamp_ms(&self) -> u64 {
+        (self.0 >> RANDOM_BITS) as u64
+    }
+
+    /// The 80-bit random part of this ULID.
+    pub const fn random(&self) -> u128 {
+        self.0 & RANDOM_MAX
+    }
+
+    /// Value of this ULID as a [u128].
+    pub const fn as_u128(&self) -> u128 {
+        self.0
+    }
+}
+
+impl fmt::Display for Ulid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let symbols = CROCKFORD.as_bytes();
+        let mut encoded = [0; Ulid::LEN];
+
+        for (position, symbol) in encoded.iter_mut().enumerate() {
+            let shift = 5 * (Ulid::LEN - 1 - position);
+
+            *symbol = symbols[(self.0 >> shift) as usize & 0x1f];
+        }
+
+        write!(
+            f,
+            "{}",
+            std::str::from_utf8(&encoded).expect("crockford is ascii")
+        )
+    }
+}
+
+impl FromStr for Ulid {
+    type Err = RandidError;
+
+    /// Parses the 26 character form of a ULID, in any case.
+    ///
+    /// As 26 base32 characters hold 130 bits, the first character must be `7` or
+    /// below for the value to fit in 128 bits.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let found = s.chars().count();
+
+        if found != Ulid::LEN {
+            return Err(RandidError::InvalidLength {
+                expected: Ulid::LEN,
+                found,
+            });
+        }
+
+        let mut value = 0u128;
+
+        for (position, character) in s.chars().enumerate() {
+            let digit = CROCKFORD
+                .find(character.to_ascii_uppercase())
+                .filter(|digit| position != 0 || *digit <= 7)
+                .ok_or(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                })?;
+
+            value = value << 5 | digit as u128;
+        }
+
+        Ok(Self(value))
+    }
+}
+
+impl From<u128> for Ulid {
+    fn from(value: u128) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Ulid> for u128 {
+    fn from(ulid: Ulid) -> Self {
+        ulid.0
+    }
+}
+
+/// A generator of strictly increasing [Ulid]s.
+///
+/// When two ULIDs are generated within the same millisecond, the random part of
+/// the previous one is incremented instead of drawing a new one, so every ULID
+/// from a single generator sorts after the last. Should the clock go backwards,
+/// the previous timestamp keeps being used until the clock catches up.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::UlidGenerator;
+///
+/// fn main() {
+///     let mut gen = UlidGenerator::new();
+///
+///     let first = gen.generate().unwrap();
+///     let second = gen.generate().unwrap();
+///
+///     assert!(first < second);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UlidGenerator {
+    last: Option<Ulid>,
+}
+
+impl UlidGenerator {
+    /// Creates a new generator which hasn't generated any ULIDs yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Generates the next ULID for the current time using [rand::thread_rng].
+    ///
+    /// # Errors
+    ///
+    /// Returns [RandidError::Overflow] if the random part can't be incremented
+    /// any further within the current millisecond, which is astronomically
+    /// unlikely with 80 random bits.
+    pub fn generate(&mut self) -> Result<Ulid, RandidError> {
+        self.generate_at(clock::now_millis(), &mut rand::thread_rng())
+    }
+
+    /// Generates the next ULID for the given number of milliseconds since the Unix
+    /// epoch using the provided random number generator.
+    ///
+    /// Errors are the same as [UlidGenerator::generate].
+    pub fn generate_at<R: RngCore + ?Sized>(
+        &mut self,
+        unix_ms: u64,
+        rng: &mut R,
+    ) -> Result<Ulid, RandidError> {
+        let next = match self.last {
+            Some(last) if last.timestamp_ms() >= unix_ms & TIMESTAMP_MAX => {
+                if last.random() == RANDOM_MAX {
+                    return Err(RandidError::Overflow);
+                }
+
+                Ulid(last.0 + 1)
+            }
+            _ => Ulid::at(unix_ms, rng),
+        };
+
+        self.last = Some(next);
+
+        Ok(next)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks parsing and formatting against the example of the ULID spec
+    #[test]
+    fn spec_example() {
+        let ulid: Ulid = "01ARZ3NDEKTSV4RRFFQ69G5FAV".parse().unwrap();
+
+        assert_eq!(1469922850259, ulid.timestamp_ms());
+        assert_eq!("01ARZ3NDEKTSV4RRFFQ69G5FAV", ulid.to_string());
+        assert_eq!(ulid, "01arz3ndektsv4rrffq69g5fav".parse().unwrap());
+    }
+
+    /// Checks timestamps and random parts survive a round trip
+    #[test]
+    fn parts_round_trip() {
+        let ulid = Ulid::from_parts(TIMESTAMP_MAX, RANDOM_MAX);
+
+        assert_eq!("7ZZZZZZZZZZZZZZZZZZZZZZZZZ", ulid.to_string());
+        assert_eq!(TIMESTAMP_MAX, ulid.timestamp_ms());
+        assert_eq!(RANDOM_MAX, ulid.random());
+        assert_eq!("00000000000000000000000000", Ulid::default().to_string());
+    }
+
+    /// Checks malformed text is rejected, including values above 128 bits
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(RandidError::InvalidLength {
+                expected: 26,
+                found: 3
+            }),
+            "abc".parse::<Ulid>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '8',
+                position: 0
+            }),
+            "8ZZZZZZZZZZZZZZZZZZZZZZZZZ".parse::<Ulid>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: 'U',
+                position: 2
+            }),
+            "01UZ3NDEKTSV4RRFFQ69G5FAV0".parse::<Ulid>()
+        );
+    }
+
+    /// Checks the monotonic generator increments within a millisecond and
+    /// doesn't go backwards with the clock
+    #[test]
+    fn monotonic() {
+        let mut rng = rand::thread_rng();
+        let mut gen = UlidGenerator::new();
+
+        let first = gen.generate_at(1_000, &mut rng).unwrap();
+        let second = gen.generate_at(1_000, &mut rng).unwrap();
+        let regressed = gen.generate_at(999, &mut rng).unwrap();
+        let later = gen.generate_at(1_001, &mut rng).unwrap();
+
+        assert_eq!(first.as_u128() + 1, second.as_u128());
+        assert_eq!(second.as_u128() + 1, regressed.as_u128());
+        assert_eq!(1_001, later.timestamp_ms());
+    }
+
+    /// Checks the monotonic generator errors once the random part is exhausted
+    #[test]
+    fn monotonic_overflow() {
+        let mut gen = UlidGenerator {
+            last: Some(Ulid::from_parts(1_000, RANDOM_MAX)),
+        };
+
+        assert_eq!(
+            Err(RandidError::Overflow),
+            gen.generate_at(1_000, &mut rand::thread_rng())
+        );
+    }
+}
@@ -0,0 +1,319 @@
+//! Generation of IDs which are guaranteed not to repeat, see [UniqueGenerator].
+
+use crate::generator::DEFAULT_MAX_RETRIES;
+use crate::{BloomFilter, IdGenerator, RandidError};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt;
+
+/// A record of every ID issued by a [UniqueGenerator], which it checks new IDs
+/// against.
+///
+/// Implementations are provided for a `HashSet<String>`, which is exact but
+/// grows with every ID, and a [BloomFilter], which has a fixed size but
+/// occasionally reports unseen IDs as duplicates. Neither can fail, so their
+/// [UniquenessStore::Error] is [Infallible]. Implement it yourself to check
+/// against a database or other shared storage, reporting any failure to reach it
+/// as an error rather than guessing whether the ID is new.
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{IdGenerator, UniqueError, UniqueGenerator, UniquenessStore};
+/// use std::collections::HashSet;
+/// use std::io;
+///
+/// /// Store standing in for a table with a unique index, which may be offline
+/// struct Table {
+///     rows: HashSet<String>,
+///     online: bool,
+/// }
+///
+/// impl UniquenessStore for Table {
+///     type Error = io::Error;
+///
+///     fn insert(&mut self, id: &str) -> Result<bool, io::Error> {
+///         if !self.online {
+///             return Err(io::Error::new(io::ErrorKind::NotConnected, "table is offline"));
+///         }
+///
+///         Ok(self.rows.insert(id.to_string()))
+///     }
+/// }
+///
+/// fn main() {
+///     let gen = IdGenerator::builder().length(8).build().unwrap();
+///     let table = Table { rows: HashSet::new(), online: true };
+///     let mut unique = UniqueGenerator::new(gen, table);
+///
+///     assert!(unique.generate().is_ok());
+///
+///     unique.store_mut().online = false;
+///
+///     match unique.generate() {
+///         Err(UniqueError::Store(err)) => assert_eq!(err.kind(), io::ErrorKind::NotConnected),
+///         other => panic!("expected a store error, got {:?}", other),
+///     }
+/// }
+/// ```
+pub trait UniquenessStore {
+    /// Error returned when the store can't be checked or updated.
+    type Error;
+
+    /// Records `id` as issued, returning `Ok(true)` if it's new or `Ok(false)` if
+    /// it has already been issued and must not be used again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if it couldn't be determined whether `id` is new, in
+    /// which case `id` must not be used.
+    fn insert(&mut self, id: &str) -> Result<bool, Self::Error>;
+}
+
+impl UniquenessStore for HashSet<String> {
+    type Error = Infallible;
+
+    fn insert(&mut self, id: &str) -> Result<bool, Infallible> {
+        Ok(!self.contains(id) && HashSet::insert(self, id.to_string()))
+    }
+}
+
+impl UniquenessStore for BloomFilter {
+    type Error = Infallible;
+
+    fn insert(&mut self, id: &str) -> Result<bool, Infallible> {
+        Ok(BloomFilter::insert(self, id))
+    }
+}
+
+impl<S: UniquenessStore + ?Sized> UniquenessStore for &mut S {
+    type Error = S::Error;
+
+    fn insert(&mut self, id: &str) -> Result<bool, S::Error> {
+        (**self).insert(id)
+    }
+}
+
+impl<S: UniquenessStore + ?Sized> UniquenessStore for Box<S> {
+    type Error = S::Error;
+
+    fn insert(&mut self, id: &str) -> Result<bool, S::Error> {
+        (**self).insert(id)
+    }
+}
+
+/// Reasons a [UniqueGenerator] may fail to produce an ID, where `E` is the
+/// [UniquenessStore::Error] of its store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UniqueError<E> {
+    /// No new ID could be produced, such as
+    /// [RandidError::UniquenessExhausted] when every attempt was a duplicate.
+    Randid(RandidError),
+    /// The store couldn't be checked or updated.
+    Store(E),
+}
+
+impl<E: fmt::Display> fmt::Display for UniqueError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UniqueError::Randid(err) => write!(f, "{}", err),
+            UniqueError::Store(err) => write!(f, "uniqueness store failed, {}", err),
+        }
+    }
+}
+
+impl<E: Error + 'static> Error for UniqueError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UniqueError::Randid(err) => Some(err),
+            UniqueError::Store(err) => Some(err),
+        }
+    }
+}
+
+impl<E> From<RandidError> for UniqueError<E> {
+    fn from(err: RandidError) -> Self {
+        UniqueError::Randid(err)
+    }
+}
+
+impl From<UniqueError<Infallible>> for RandidError {
+    /// Unwraps the error of a generator whose store can't fail.
+    fn from(err: UniqueError<Infallible>) -> Self {
+        match err {
+            UniqueError::Randid(err) => err,
+            UniqueError::Store(never) => match never {},
+        }
+    }
+}
+
+/// An [IdGenerator] which never returns the same ID twice, by recording every ID
+/// in a [UniquenessStore] and regenerating any which was already issued.
+///
+/// Uniqueness only holds for IDs checked against the same store, so a store
+/// shared between processes, such as a database, is needed for uniqueness across
+/// them. With a long enough length collisions are rare, so retries are cheap;
+/// running out of retries usually means the length is too short for the number
+/// of IDs, see [IdGenerator::analysis].
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::{IdGenerator, UniqueGenerator};
+/// use std::collections::HashSet;
+///
+/// fn main() {
+///     let gen = IdGenerator::builder().length(8).build().unwrap();
+///     let mut unique = UniqueGenerator::new(gen, HashSet::new());
+///
+///     let first = unique.generate().unwrap();
+///     let second = unique.generate().unwrap();
+///
+///     assert_ne!(first, second);
+///     assert_eq!(unique.store().len(), 2);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UniqueGenerator<S> {
+    generator: IdGenerator,
+    store: S,
+    max_retries: usize,
+}
+
+impl<S: UniquenessStore> UniqueGenerator<S> {
+    /// Wraps `generator`, checking its IDs against `store`.
+    pub fn new(generator: IdGenerator, store: S) -> Self {
+        Self {
+            generator,
+            store,
+            max_retries: DEFAULT_MAX_RETRIES,
+        }
+    }
+
+    /// Sets how many times an already-issued ID is regenerated before giving up,
+    /// defaulting to [DEFAULT_MAX_RETRIES](crate::DEFAULT_MAX_RETRIES).
+    pub fn max_retries(mut self, max_retries: usize) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Generates a new ID which hasn't been issued before and records it in the
+    /// store.
+    ///
+    /// # Errors
+    ///
+    /// Returns [UniqueError::Randid] holding
+    /// [RandidError::UniquenessExhausted] if the initial ID and every retry had
+    /// already been issued, or any error from [IdGenerator::try_generate].
+    /// Returns [UniqueError::Store] as soon as the store fails. Stores which
+    /// can't fail give a `UniqueError<Infallible>`, which converts into a
+    /// [RandidError].
+    pub fn generate(&mut self) -> Result<String, UniqueError<S::Error>> {
+        for _ in 0..=self.max_retries {
+            let id = self.generator.try_generate()?;
+
+            if self.store.insert(&id).map_err(UniqueError::Store)? {
+                return Ok(id);
+            }
+        }
+
+        Err(RandidError::UniquenessExhausted {
+            attempts: self.max_retries + 1,
+        }
+        .into())
+    }
+
+    /// The wrapped generator.
+    pub fn generator(&self) -> &IdGenerator {
+        &self.generator
+    }
+
+    /// The store of issued IDs.
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The store of issued IDs, mutably, such as for recording IDs issued
+    /// elsewhere.
+    pub fn store_mut(&mut self) -> &mut S {
+        &mut self.store
+    }
+
+    /// Consumes this generator, returning its store so it can be persisted.
+    pub fn into_store(self) -> S {
+        self.store
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Alphabet;
+
+    /// Checks every possible id is issued exactly once before running out
+    #[test]
+    fn exhausts_space() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::new("ab").unwrap())
+            .length(3)
+            .build()
+            .unwrap();
+        let mut unique = UniqueGenerator::new(gen, HashSet::new()).max_retries(10_000);
+
+        for _ in 0..8 {
+            unique.generate().unwrap();
+        }
+
+        assert_eq!(8, unique.store().len());
+        assert_eq!(
+            Err(RandidError::UniquenessExhausted { attempts: 10_001 }),
+            unique.generate().map_err(RandidError::from)
+        );
+    }
+
+    /// Checks ids recorded externally and through a borrowed bloom filter are
+    /// never issued
+    #[test]
+    fn shared_store() {
+        let gen = IdGenerator::builder()
+            .alphabet(Alphabet::new("ab").unwrap())
+            .length(1)
+            .build()
+            .unwrap();
+        let mut filter = BloomFilter::new(1024, 3);
+        filter.insert("a");
+
+        let mut unique = UniqueGenerator::new(gen, &mut filter).max_retries(1_000);
+
+        assert_eq!("b", unique.generate().unwrap());
+        assert!(unique.generate().is_err());
+        assert!(filter.contains("b"));
+    }
+
+    /// Checks store failures are returned immediately and the id isn't issued
+    #[test]
+    fn store_error() {
+        /// Store which fails after accepting a fixed number of ids
+        struct Flaky(usize);
+
+        impl UniquenessStore for Flaky {
+            type Error = &'static str;
+
+            fn insert(&mut self, _: &str) -> Result<bool, &'static str> {
+                self.0 = self.0.checked_sub(1).ok_or("store is down")?;
+
+                Ok(true)
+            }
+        }
+
+        let mut unique = UniqueGenerator::new(IdGenerator::builder().build().unwrap(), Flaky(1));
+
+        assert!(unique.generate().is_ok());
+        assert_eq!(Err(UniqueError::Store("store is down")), unique.generate());
+        assert_eq!(
+            "uniqueness store failed, store is down",
+            unique.generate().unwrap_err().to_string()
+        );
+    }
+}
@@ -0,0 +1,300 @@
+//! Standard [RFC 9562](https://www.rfc-editor.org/rfc/rfc9562) UUIDs, see [Uuid].
+
+use crate::base62;
+use crate::clock;
+use crate::RandidError;
+use rand::RngCore;
+use std::fmt;
+use std::str::FromStr;
+
+/// Character positions of the hyphens in the hyphenated form of a [Uuid].
+const HYPHENS: [usize; 4] = [8, 13, 18, 23];
+
+/// A 128-bit universally unique identifier.
+///
+/// Random (version 4) and time-ordered (version 7) UUIDs can be generated using
+/// [rand::thread_rng] or any other random number generator, in the same way as
+/// [IdGenerator::generate_with_rng](crate::IdGenerator::generate_with_rng). Each
+/// UUID can be rendered in three forms:
+///
+/// - Hyphenated, like `919108f7-52d1-4320-9bac-f847db4148a8`, which is also used
+///   for [Display](fmt::Display)
+/// - Simple, like `919108f752d143209bacf847db4148a8`
+/// - BASE62, like `4QgAS76dLuYGIOevxRNdwe`, which is always 22 characters and
+///   uses the same url-safe alphabet as [randid_string](crate::randid_string)
+///
+/// ## Examples
+///
+/// ```rust
+/// use randid::Uuid;
+///
+/// fn main() {
+///     let id = Uuid::new_v7();
+///
+///     assert_eq!(id.version(), 7);
+///     assert_eq!(id.to_hyphenated().len(), 36);
+///     assert_eq!(id.to_base62().len(), 22);
+///     assert_eq!(id, id.to_string().parse().unwrap());
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// The nil UUID, with all bits set to zero.
+    pub const NIL: Uuid = Uuid([0; 16]);
+
+    /// Generates a random version 4 UUID using [rand::thread_rng].
+    pub fn new_v4() -> Self {
+        Self::new_v4_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a random version 4 UUID using the provided random number
+    /// generator.
+    pub fn new_v4_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        let mut bytes = [0; 16];
+        rng.fill_bytes(&mut bytes);
+
+        Self::with_version(bytes, 4)
+    }
+
+    /// Generates a time-ordered version 7 UUID for the current time using
+    /// [rand::thread_rng].
+    pub fn new_v7() -> Self {
+        Self::new_v7_with_rng(&mut rand::thread_rng())
+    }
+
+    /// Generates a time-ordered version 7 UUID for the current time using the
+    /// provided random number generator.
+    pub fn new_v7_with_rng<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self::new_v7_at(clock::now_millis(), rng)
+    }
+
+    /// Generates a time-ordered version 7 UUID for the given number of
+    /// milliseconds since the Unix epoch, only the lower 48 bits of which are used.
+    ///
+    /// UUIDs generated for a later time always sort after those for an earlier
+    /// one, whilst those sharing a millisecond are ordered randomly.
+    pub fn new_v7_at<R: RngCore + ?Sized>(unix_ms: u64, rng: &mut R) -> Self {
+        let mut bytes = [0; 16];
+        rng.fill_bytes(&mut bytes[6..]);
+        bytes[..6].copy_from_slice(&unix_ms.to_be_bytes()[2..]);
+
+        Self::with_version(bytes, 7)
+    }
+
+    /// Creates a UUID from its raw big-endian bytes, without checking the version
+    /// or variant.
+    pub const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+
+    /// Creates a UUID from its value as a big-endian [u128].
+    pub const fn from_u128(value: u128) -> Self {
+        Self(value.to_be_bytes())
+    }
+
+    /// Raw big-endian bytes of this UUID.
+    pub fn as_bytes(&self) -> &[u8; 16] {
+        &self.0
+    }
+
+    /// Value of this UUID as a big-endian [u128].
+    pub fn as_u128(&self) -> u128 {
+        u128::from_be_bytes(self.0)
+    }
+
+    /// Version number stored in this UUID, such as `4` or `7`.
+    pub fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+
+    /// Milliseconds since the Unix epoch this UUID was generated at, if it's a
+    /// version 7 UUID.
+    pub fn timestamp_ms(&self) -> Option<u64> {
+        if self.version() != 7 {
+            return None;
+        }
+
+        let mut ms = [0; 8];
+        ms[2..].copy_from_slice(&self.0[..6]);
+
+        Some(u64::from_be_bytes(ms))
+    }
+
+    /// Hyphenated lowercase form, like `919108f7-52d1-4320-9bac-f847db4148a8`.
+    pub fn to_hyphenated(&self) -> String {
+        let simple = self.to_simple();
+        let mut hyphenated = String::with_capacity(36);
+
+        for (position, c) in simple.chars().enumerate() {
+            if [8, 12, 16, 20].contains(&position) {
+                hyphenated.push('-');
+            }
+
+            hyphenated.push(c);
+        }
+
+        hyphenated
+    }
+
+    /// Simple lowercase form without hyphens, like
+    /// `919108f752d143209bacf847db4148a8`.
+    pub fn to_simple(&self) -> String {
+        format!("{:032x}", self.as_u128())
+    }
+
+    /// BASE62 form, always 22 characters long, like `4QgAS76dLuYGIOevxRNdwe`.
+    pub fn to_base62(&self) -> String {
+        base62::encode_u128_padded(self.as_u128(), 22)
+    }
+
+    /// Sets the version and RFC 9562 variant bits of `bytes`.
+    fn with_version(mut bytes: [u8; 16], version: u8) -> Self {
+        bytes[6] = (bytes[6] & 0x0f) | (version << 4);
+        bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+        Self(bytes)
+    }
+}
+
+impl fmt::Display for Uuid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.to_hyphenated())
+    }
+}
+
+impl FromStr for Uuid {
+    type Err = RandidError;
+
+    /// Parses the hyphenated or simple forms of a UUID, in any case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hyphenated = match s.chars().count() {
+            36 => true,
+            32 => false,
+            found => {
+                return Err(RandidError::InvalidLength {
+                    expected: 36,
+                    found,
+                })
+            }
+        };
+        let mut value = 0u128;
+
+        for (position, character) in s.chars().enumerate() {
+            if hyphenated && HYPHENS.contains(&position) {
+                if character != '-' {
+                    return Err(RandidError::InvalidCharacter {
+                        character,
+                        position,
+                    });
+                }
+
+                continue;
+            }
+
+            let digit = character
+                .to_digit(16)
+                .ok_or(RandidError::InvalidCharacter {
+                    character,
+                    position,
+                })?;
+
+            value = value << 4 | digit as u128;
+        }
+
+        Ok(Self::from_u128(value))
+    }
+}
+
+impl From<u128> for Uuid {
+    fn from(value: u128) -> Self {
+        Self::from_u128(value)
+    }
+}
+
+impl From<Uuid> for u128 {
+    fn from(uuid: Uuid) -> Self {
+        uuid.as_u128()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Checks the version and variant bits of generated UUIDs
+    #[test]
+    fn version_variant() {
+        let v4 = Uuid::new_v4();
+        let v7 = Uuid::new_v7();
+
+        assert_eq!(4, v4.version());
+        assert_eq!(7, v7.version());
+        assert_eq!(0x80, v4.as_bytes()[8] & 0xc0);
+        assert_eq!(0x80, v7.as_bytes()[8] & 0xc0);
+        assert_eq!(None, v4.timestamp_ms());
+    }
+
+    /// Checks parsing and formatting against the examples of RFC 9562
+    #[test]
+    fn rfc_examples() {
+        let v4: Uuid = "919108f7-52d1-4320-9bac-f847db4148a8".parse().unwrap();
+        let v7: Uuid = "017F22E2-79B0-7CC3-98C4-DC0C0C07398F".parse().unwrap();
+
+        assert_eq!(4, v4.version());
+        assert_eq!("919108f752d143209bacf847db4148a8", v4.to_simple());
+        assert_eq!("4QgAS76dLuYGIOevxRNdwe", v4.to_base62());
+        assert_eq!(Some(0x017F22E279B0), v7.timestamp_ms());
+        assert_eq!("017f22e2-79b0-7cc3-98c4-dc0c0c07398f", v7.to_string());
+        assert_eq!(v7, v7.to_simple().parse().unwrap());
+    }
+
+    /// Checks version 7 UUIDs sort by their timestamp
+    #[test]
+    fn v7_ordering() {
+        let mut rng = rand::thread_rng();
+        let earlier = Uuid::new_v7_at(1_000, &mut rng);
+        let later = Uuid::new_v7_at(1_001, &mut rng);
+
+        assert!(earlier < later);
+        assert_eq!(Some(1_000), earlier.timestamp_ms());
+    }
+
+    /// Checks the BASE62 form is fixed-width
+    #[test]
+    fn base62_width() {
+        assert_eq!("0000000000000000000000", Uuid::NIL.to_base62());
+        assert_eq!(
+            "7n42DGM5Tflk9n8mt7Fhc7",
+            Uuid::from_u128(u128::MAX).to_base62()
+        );
+        assert_eq!(22, Uuid::new_v4().to_base62().len());
+    }
+
+    /// Checks malformed text is rejected with the position of the problem
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(RandidError::InvalidLength {
+                expected: 36,
+                found: 3
+            }),
+            "abc".parse::<Uuid>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: 'g',
+                position: 1
+            }),
+            "9g9108f752d143209bacf847db4148a8".parse::<Uuid>()
+        );
+        assert_eq!(
+            Err(RandidError::InvalidCharacter {
+                character: '0',
+                position: 8
+            }),
+            "919108f7052d1-4320-9bac-f847db4148a8".parse::<Uuid>()
+        );
+    }
+}